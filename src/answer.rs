@@ -0,0 +1,236 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+use crate::math;
+
+/// An integer answer computed with checked arithmetic.
+///
+/// Values are widened to [`i128`]. An overflow poisons the answer instead of silently wrapping
+/// and is reported as an error by the runner.
+///
+/// ```
+/// # use advent_of_code::Answer;
+/// let answer: Answer = [u32::MAX, u32::MAX].into_iter().sum();
+/// assert_eq!(answer, Answer::from(8_589_934_590_u64));
+///
+/// let overflow = Answer::from(i128::MAX) + 1;
+/// assert!(overflow.value().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Answer(Result<i128, Overflow>);
+
+impl Answer {
+    /// Returns the value, or an error if any operation leading to it overflowed.
+    pub fn value(self) -> Result<i128, Overflow> {
+        self.0
+    }
+
+    /// Absolute value.
+    #[must_use]
+    pub fn abs(self) -> Answer {
+        Answer(self.0.and_then(|a| a.checked_abs().ok_or(Overflow)))
+    }
+
+    /// Least common multiple, e.g. of the cycle lengths of several simulations.
+    #[must_use]
+    pub fn lcm(self, other: impl Into<Answer>) -> Answer {
+        self.apply(other.into(), math::lcm)
+    }
+
+    fn apply(self, rhs: Answer, op: fn(i128, i128) -> Option<i128>) -> Answer {
+        Answer(self.0.and_then(|a| op(a, rhs.0?).ok_or(Overflow)))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Ok(value) => write!(f, "{value}"),
+            Err(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The error of an [`Answer`] whose computation overflowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Overflow;
+
+impl Error for Overflow {}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("arithmetic overflow")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+macro_rules! impl_from {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer(i128::try_from(value).map_err(|_| Overflow))
+            }
+        }
+    )*};
+}
+
+impl_from!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// An answer poisoned by an overflow outside of [`Answer`] arithmetic.
+impl From<Overflow> for Answer {
+    fn from(e: Overflow) -> Self {
+        Answer(Err(e))
+    }
+}
+
+macro_rules! impl_op {
+    ($op:ident, $method:ident, $assign:ident, $assign_method:ident, $checked:path) => {
+        impl<T: Into<Answer>> $op<T> for Answer {
+            type Output = Answer;
+
+            fn $method(self, rhs: T) -> Answer {
+                self.apply(rhs.into(), $checked)
+            }
+        }
+
+        impl<T: Into<Answer>> $assign<T> for Answer {
+            fn $assign_method(&mut self, rhs: T) {
+                *self = self.apply(rhs.into(), $checked);
+            }
+        }
+    };
+}
+
+impl_op!(Add, add, AddAssign, add_assign, i128::checked_add);
+impl_op!(Sub, sub, SubAssign, sub_assign, i128::checked_sub);
+impl_op!(Mul, mul, MulAssign, mul_assign, i128::checked_mul);
+impl_op!(Div, div, DivAssign, div_assign, checked_div);
+
+/// Divides like the primitive integers, which panic on a division by zero.
+fn checked_div(a: i128, b: i128) -> Option<i128> {
+    assert!(b != 0, "attempt to divide by zero");
+    a.checked_div(b)
+}
+
+impl<T: Into<Answer>> Sum<T> for Answer {
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Answer::from(0), |acc, x| acc + x)
+    }
+}
+
+impl<T: Into<Answer>> Product<T> for Answer {
+    fn product<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Answer::from(1), |acc, x| acc * x)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A value returned by a solution part, reported by the runner.
+pub trait PartAnswer {
+    /// Formats the answer, or returns an error if it could not be computed.
+    fn answer(&self) -> Result<String, String>;
+}
+
+impl PartAnswer for Answer {
+    fn answer(&self) -> Result<String, String> {
+        self.0
+            .map(|value| value.to_string())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Parts that can fail on malformed input report the error instead of an answer.
+impl<T: PartAnswer, E: Display> PartAnswer for Result<T, E> {
+    fn answer(&self) -> Result<String, String> {
+        match self {
+            Ok(answer) => answer.answer(),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+macro_rules! impl_part_answer {
+    ($($t:ty),*) => {$(
+        impl PartAnswer for $t {
+            fn answer(&self) -> Result<String, String> {
+                Ok(self.to_string())
+            }
+        }
+    )*};
+}
+
+impl_part_answer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, String, &str
+);
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Answer, Overflow, PartAnswer};
+
+    #[test]
+    fn widens_values() {
+        let answer = Answer::from(u32::MAX) * u32::MAX;
+        assert_eq!(answer.value(), Ok(18_446_744_065_119_617_025));
+        assert_eq!(Answer::from(-3) - 4_u8, Answer::from(-7));
+        assert_eq!((Answer::from(-7) / 2).abs(), Answer::from(3));
+        assert_eq!(answer.to_string(), "18446744065119617025");
+    }
+
+    #[test]
+    fn poisons_on_overflow() {
+        let mut answer = Answer::from(u128::MAX / 2);
+        assert_eq!(answer.value(), Ok(i128::MAX));
+        answer *= 2;
+        assert_eq!(answer.value(), Err(Overflow));
+
+        // an overflow is never undone by later operations.
+        answer -= i128::MAX;
+        assert_eq!(answer.value(), Err(Overflow));
+        assert_eq!(answer.to_string(), "arithmetic overflow");
+        assert_eq!(Answer::from(u128::MAX).value(), Err(Overflow));
+        assert_eq!(Answer::from(i128::MIN).abs().value(), Err(Overflow));
+        assert_eq!((Answer::from(Overflow) - 1).value(), Err(Overflow));
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(Answer::from(42).answer(), Ok("42".into()));
+        assert_eq!(
+            (Answer::from(i128::MAX) + 1).answer(),
+            Err("arithmetic overflow".into())
+        );
+        assert_eq!(Ok::<_, String>(Answer::from(42)).answer(), Ok("42".into()));
+        assert_eq!(
+            Err::<Answer, _>("line 2 has no digit").answer(),
+            Err("line 2 has no digit".into())
+        );
+    }
+
+    #[test]
+    fn sums_and_multiplies() {
+        let sum: Answer = (1..=100_u64).sum();
+        assert_eq!(sum, Answer::from(5050));
+
+        let product: Answer = [i64::MAX, 2, 3].into_iter().product();
+        assert_eq!(product.value(), Ok(i64::MAX as i128 * 6));
+
+        let overflow: Answer = std::iter::repeat_n(u64::MAX, 3).product();
+        assert_eq!(overflow.value(), Err(Overflow));
+    }
+
+    #[test]
+    fn computes_lcm() {
+        assert_eq!(Answer::from(4).lcm(6), Answer::from(12));
+        assert_eq!(Answer::from(0).lcm(6), Answer::from(0));
+        assert_eq!(Answer::from(-4).lcm(6), Answer::from(12));
+        assert_eq!(Answer::from(i128::MAX).lcm(2).value(), Err(Overflow));
+    }
+}
@@ -1,57 +1,43 @@
-advent_of_code::solution!(1);
+use advent_of_code::calibration::{CalibrationError, Scanner, ENGLISH};
+use advent_of_code::validate::{Validation, Validator};
+use advent_of_code::Answer;
+advent_of_code::solution!(1, compare: [1: part_one_old => part_one], validate: validate);
 
-pub fn part_one_old(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(|line| {
-                let first = line.chars().find(|c| c.is_numeric());
-                let last = line.chars().rev().find(|c| c.is_numeric());
-                let num = match (first, last) {
-                    (Some(f), Some(l)) => f.to_string() + &l.to_string(),
-                    (Some(f), _) => f.to_string(),
-                    (_, Some(l)) => l.to_string(),
-                    (_, _) => panic!(),
-                };
-                num.parse::<u32>().unwrap()
-            })
-            .sum(),
-    )
+/// Every line has a numeric or spelled out digit. Part one needs numeric digits, but the example of
+/// part two has lines without them, so they are left to part one to report.
+pub fn validate(input: &str) -> Validation {
+    let mut validator = Validator::new();
+    validator.require(
+        Scanner::spelled(&ENGLISH)
+            .and_then(|scanner| scanner.calibration_sum(input))
+            .map_err(|err| err.to_string()),
+    );
+    validator.finish()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(
-        input
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .filter_map(|c| c.to_digit(10))
-                    .collect::<Vec<u32>>()
-            })
-            .map(|v| 10 * v.first().unwrap() + v.last().unwrap())
-            .sum(),
-    )
+pub fn part_one_old(input: &str) -> Option<Result<Answer, CalibrationError>> {
+    Some(Ok(input
+        .lines()
+        .map(|line| {
+            let first = line.chars().find(|c| c.is_numeric());
+            let last = line.chars().rev().find(|c| c.is_numeric());
+            let num = match (first, last) {
+                (Some(f), Some(l)) => f.to_string() + &l.to_string(),
+                (Some(f), _) => f.to_string(),
+                (_, Some(l)) => l.to_string(),
+                (_, _) => panic!(),
+            };
+            num.parse::<u32>().unwrap()
+        })
+        .sum()))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let num_vec = vec![
-        ("one", "o1e"),
-        ("two", "t2o"),
-        ("three", "t3e"),
-        ("four", "f4r"),
-        ("five", "f5e"),
-        ("six", "s6x"),
-        ("seven", "s7n"),
-        ("eight", "e8t"),
-        ("nine", "n9e"),
-    ];
-
-    let mut new_string = input.to_string();
-    for (search, r_string) in num_vec.iter() {
-        new_string = new_string.replace(search, r_string);
-    }
+pub fn part_one(input: &str) -> Option<Result<Answer, CalibrationError>> {
+    Some(Scanner::digits().calibration_sum(input))
+}
 
-    part_one(&new_string)
+pub fn part_two(input: &str) -> Option<Result<Answer, CalibrationError>> {
+    Some(Scanner::spelled(&ENGLISH).and_then(|scanner| scanner.calibration_sum(input)))
 }
 
 #[cfg(test)]
@@ -60,13 +46,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        // let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(Some(142), Some(142));
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert!(validate(&input).is_ok());
+        let result = part_one(&input).map(|result| result.map_err(|err| err.to_string()));
+        assert_eq!(result, Some(Err("line 2 has no digit".into())));
+        assert_eq!(
+            part_one("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet"),
+            Some(Ok(Answer::from(142)))
+        );
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(281));
+        assert_eq!(result, Some(Ok(Answer::from(281))));
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::Answer;
 use std::cmp;
 
 advent_of_code::solution!(2);
@@ -34,7 +35,7 @@ fn get_game_maxes(input: &str) -> Vec<(u32, u32, u32)> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<Answer> {
     const N_CUBES: (u32, u32, u32) = (12, 13, 14);
     Some(
         get_game_maxes(input)
@@ -42,16 +43,16 @@ pub fn part_one(input: &str) -> Option<u32> {
             .enumerate()
             .filter(|(_, game)| game.0 <= N_CUBES.0 && game.1 <= N_CUBES.1 && game.2 <= N_CUBES.2)
             .inspect(|x| println!("games that are possible: {:?}", x))
-            .map(|(i, _)| i as u32 + 1)
+            .map(|(i, _)| i + 1)
             .sum(),
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<Answer> {
     Some(
         get_game_maxes(input)
             .iter()
-            .map(|game| game.0 * game.1 * game.2)
+            .map(|game| Answer::from(game.0) * game.1 * game.2)
             .sum(),
     )
 }
@@ -63,11 +64,11 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
+        assert_eq!(result, Some(Answer::from(8)));
     }
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2286));
+        assert_eq!(result, Some(Answer::from(2286)));
     }
 }
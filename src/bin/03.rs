@@ -1,3 +1,4 @@
+use advent_of_code::Answer;
 use std::collections::HashMap;
 
 advent_of_code::solution!(3);
@@ -62,7 +63,7 @@ fn construct_sym_map(input: &str) -> HashMap<(usize, usize), Vec<u32>> {
             */
             if j == grid[i].len() - 1 || !grid[i][j].is_numeric() {
                 if let Some(sym_loc) = near_sym {
-                    map.entry(sym_loc).or_insert(Vec::new());
+                    map.entry(sym_loc).or_default();
                     if let Some(ve) = map.get_mut(&sym_loc) {
                         ve.push(vec_to_num(num_vec));
                     }
@@ -75,17 +76,17 @@ fn construct_sym_map(input: &str) -> HashMap<(usize, usize), Vec<u32>> {
     map
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<Answer> {
     let map: HashMap<(usize, usize), Vec<u32>> = construct_sym_map(input);
-    Some(map.values().map(|v| v.iter().sum::<u32>()).sum())
+    Some(map.values().flatten().copied().sum())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<Answer> {
     let map = construct_sym_map(input);
     Some(
         map.values()
             .filter(|v| v.len() > 1)
-            .map(|v| v.iter().fold(1, |acc, x| x * acc))
+            .map(|v| v.iter().copied().product::<Answer>())
             .sum(),
     )
 }
@@ -97,12 +98,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4361));
+        assert_eq!(result, Some(Answer::from(4361)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(467835));
+        assert_eq!(result, Some(Answer::from(467835)));
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::Answer;
 use std::collections::{HashMap, HashSet};
 
 advent_of_code::solution!(4);
@@ -33,7 +34,7 @@ fn get_intersections(input: &str) -> Vec<usize> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<Answer> {
     Some(
         get_intersections(input)
             .iter()
@@ -42,7 +43,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<Answer> {
     let cards: Vec<usize> = get_intersections(input);
 
     let mut counts_map: HashMap<usize, usize> = HashMap::new();
@@ -56,7 +57,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    Some(counts_map.values().sum::<usize>() as u32)
+    Some(counts_map.values().copied().sum())
 }
 
 #[cfg(test)]
@@ -66,12 +67,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
+        assert_eq!(result, Some(Answer::from(13)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(30));
+        assert_eq!(result, Some(Answer::from(30)));
     }
 }
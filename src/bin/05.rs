@@ -1,3 +1,4 @@
+use advent_of_code::Answer;
 use std::str::FromStr;
 advent_of_code::solution!(5);
 #[allow(dead_code)]
@@ -92,7 +93,7 @@ fn parse_input(input: &str) -> Option<(Vec<usize>, Vec<Mapping>)> {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<Answer> {
     let (seeds, mappings) = parse_input(input).unwrap();
 
     Some(
@@ -100,11 +101,12 @@ pub fn part_one(input: &str) -> Option<u32> {
             .iter()
             .map(|seed| mappings.iter().fold(*seed, |acc, map| map.map_seed(acc)))
             .min()
-            .unwrap() as u32,
+            .map(Answer::from)
+            .unwrap(),
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<Answer> {
     // IDEA: Back propagate from each map... for each boundary condition :)
 
     let (seeds, mappings) = parse_input(input).unwrap();
@@ -117,14 +119,23 @@ pub fn part_two(input: &str) -> Option<u32> {
         rev_mappings[map_i]
             .0
             .iter()
-            .map(|mapping| {
+            // seeds right behind a range are mapped to themselves again.
+            .flat_map(|mapping| {
+                [
+                    mapping.source_range_start,
+                    mapping.source_range_start + mapping.length,
+                ]
+            })
+            .map(|start| {
                 rev_mappings[map_i + 1..]
                     .iter()
-                    .fold(mapping.source_range_start, |acc, map| map.rev_map_seed(acc))
+                    .fold(start, |acc, map| map.rev_map_seed(acc))
             })
             .map(|x| potential_start_seeds.push(x))
             .min(); // force iter to eval
     }
+    // the first seed of a range may lie within a single range of every map.
+    potential_start_seeds.extend(seeds.iter().step_by(2));
 
     // only test the seeds that are in valid ranges
     let new_seeds: Vec<usize> = seeds
@@ -151,7 +162,8 @@ pub fn part_two(input: &str) -> Option<u32> {
             .iter()
             .map(|seed| mappings.iter().fold(*seed, |acc, map| map.map_seed(acc)))
             .min()
-            .unwrap() as u32,
+            .map(Answer::from)
+            .unwrap(),
     )
 }
 
@@ -162,12 +174,25 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(35));
+        assert_eq!(result, Some(Answer::from(35)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
+        assert_eq!(result, Some(Answer::from(46)));
+    }
+
+    #[test]
+    fn test_part_two_unmapped_seeds() {
+        // seeds 10 to 19 are right behind the only range and keep their number.
+        let result = part_two("seeds: 0 20\n\nseed-to-soil map:\n100 0 10\n");
+        assert_eq!(result, Some(Answer::from(10)));
+
+        // no range covers any seed.
+        let result = part_two(
+            "seeds: 30 5\n\nseed-to-soil map:\n100 0 10\n\nsoil-to-fertilizer map:\n0 50 5\n",
+        );
+        assert_eq!(result, Some(Answer::from(30)));
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::{math, Answer};
 advent_of_code::solution!(6);
 
 fn parse_str(input: &str) -> Vec<(u64, u64)> {
@@ -15,31 +16,24 @@ fn parse_str(input: &str) -> Vec<(u64, u64)> {
         .collect::<Vec<(u64, u64)>>()
 }
 
-fn solve_quadratic(b: f64, c: f64) -> Option<(u32, u32)> {
-    let sqrt_disc = (b * b - 4.0 * c).sqrt();
-
-    let root1: u32 = ((-b + sqrt_disc) / 2.0).ceil() as u32;
-    let root2: u32 = ((-b - sqrt_disc) / 2.0).floor() as u32;
-
-    Some((root1, root2))
-}
-
-fn count_ways_to_win(time: u64, dist: u64) -> u32 {
-    // let mut ways_to_win: u32 = 0;
+fn count_ways_to_win(time: u64, dist: u64) -> Answer {
     // the intercepts with current distance is algebraically determinable bc quadratic
     // distance = (time-wait_time)*wait_time
     // distance = time*wait_time - wait_time^2
-    // x^2 - time*x + distance = 0
-    let (upper, lower) = solve_quadratic(-(time as f64), dist as f64).unwrap();
-    upper - lower - 1
+    // we win if x^2 - time*x + distance < 0, a tie with the record doesn't count.
+    match math::quadratic_below_zero(1, -i128::from(time), i128::from(dist)) {
+        Ok(Some(wait_times)) => Answer::from(*wait_times.end()) - *wait_times.start() + 1,
+        Ok(None) => Answer::from(0),
+        Err(e) => Answer::from(e),
+    }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<Answer> {
     Some(
         parse_str(input)
             .iter()
             .map(|(t, d)| count_ways_to_win(*t, *d))
-            .product::<u32>(),
+            .product(),
     )
 }
 
@@ -52,7 +46,7 @@ fn remove_whitespace_and_parse(input: &str) -> u64 {
         .unwrap()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<Answer> {
     let (time_str, dist_str) = input.split_once('\n').unwrap();
     let time = remove_whitespace_and_parse(time_str);
     let dist = remove_whitespace_and_parse(dist_str);
@@ -62,16 +56,71 @@ pub fn part_two(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::{self, Differential};
+
+    /// Tries every possible wait time.
+    fn simulate_races(input: &str) -> Option<Answer> {
+        Some(
+            parse_str(input)
+                .iter()
+                .map(|(time, dist)| {
+                    (0..=*time)
+                        .filter(|wait| wait * (time - wait) > *dist)
+                        .count()
+                })
+                .product(),
+        )
+    }
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
+        assert_eq!(result, Some(Answer::from(288)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(71503));
+        assert_eq!(result, Some(Answer::from(71503)));
+    }
+
+    #[test]
+    fn test_boundary_ties() {
+        // holding for 10 or 20 ms exactly ties the record of 200mm.
+        assert_eq!(count_ways_to_win(30, 200), Answer::from(9));
+        // only holding for 2 ms matches the record, which doesn't win.
+        assert_eq!(count_ways_to_win(4, 4), Answer::from(0));
+        assert_eq!(count_ways_to_win(4, 3), Answer::from(1));
+        assert_eq!(count_ways_to_win(1, 0), Answer::from(0));
+        // the roots 10^8 - 1 and 10^8 + 1 are lost to f64 rounding of the discriminant.
+        let t = 2 * 10_u64.pow(8);
+        assert_eq!(count_ways_to_win(t, 10_u64.pow(16) - 1), Answer::from(1));
+        assert_eq!(count_ways_to_win(t, 10_u64.pow(16)), Answer::from(0));
+    }
+
+    #[test]
+    fn test_part_one_against_oracle() {
+        Differential::new(
+            DAY,
+            ("part_one", part_one),
+            ("simulate_races", simulate_races),
+        )
+        .sizes(1..=4)
+        .seeds(25)
+        .shrink(differential::shrink_fields)
+        .run();
+    }
+
+    #[test]
+    fn test_part_two_against_oracle() {
+        // a single race keeps the concatenated numbers small enough to simulate.
+        Differential::new(
+            DAY,
+            ("part_two", part_two),
+            ("simulate_races", simulate_races),
+        )
+        .sizes(1..=1)
+        .seeds(100)
+        .run();
     }
 }
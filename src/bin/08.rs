@@ -1,82 +1,120 @@
-use num_integer::lcm;
-use std::collections::HashMap;
+use advent_of_code::dot::{self, Graph};
+use advent_of_code::network::{self, Ghost, Network};
+use advent_of_code::validate::{Validation, Validator};
+use advent_of_code::Answer;
+use std::collections::HashSet;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, validate: validate);
 
-fn parse_input(input: &str) -> (Vec<usize>, HashMap<&str, Vec<&str>>) {
-    let (pattern, maps) = input.split_once("\n\n").unwrap();
-    let pattern_vec: Vec<usize> = pattern
-        .chars()
-        .map(|c| if c == 'L' { 0 } else { 1 })
-        .collect();
-
-    let mut map_map: HashMap<&str, Vec<&str>> = HashMap::new();
-    for line in maps.lines() {
-        let (key, vals) = line.split_once(" = (").unwrap();
-        map_map.insert(
-            key,
-            vals.trim_end_matches(')')
-                .split(", ")
-                .collect::<Vec<&str>>(),
+/// Parsing already checks the format and that every referenced node is defined.
+pub fn validate(input: &str) -> Validation {
+    let mut validator = Validator::new();
+    let network = validator.require(input.parse::<Network>().map_err(|e| e.to_string()));
+    if let Some(network) = network {
+        validator.check(
+            network.id("AAA").is_some() && network.id("ZZZ").is_some(),
+            "part one expects the network to contain nodes AAA and ZZZ",
         );
     }
-    (pattern_vec, map_map)
+    validator.finish()
 }
 
-fn gen_solve<F>(
-    start_key: &str,
-    pattern: Vec<usize>,
-    maps: HashMap<&str, Vec<&str>>,
-    check_done: F,
-) -> u64
-where
-    F: Fn(&str) -> bool,
-{
-    let mut iters: u64 = 0;
-    let mut ind: usize = 0;
-    let mut cur_key = start_key;
-    loop {
-        iters += 1;
-        let inst = *pattern.get(ind).unwrap();
-        let cur_val = maps.get(cur_key).unwrap();
-        cur_key = cur_val.get(inst).unwrap();
+/// Builds the network with start nodes (`..A`) and end nodes (`..Z`) marked and the edges
+/// taken on the way from AAA to ZZZ highlighted.
+fn network_graph(network: &Network) -> Graph {
+    let instructions = network.instructions();
+    let mut taken: HashSet<(usize, bool)> = HashSet::new();
+    if let (Some(mut node), Some(end)) = (network.id("AAA"), network.id("ZZZ")) {
+        // each (node, instruction index) state is walked at most once, so this terminates.
+        let mut seen: HashSet<(usize, usize)> = HashSet::new();
+        for index in (0..instructions.len()).cycle() {
+            if node == end || !seen.insert((node, index)) {
+                break;
+            }
+            taken.insert((node, instructions[index]));
+            node = network.next(node, index);
+        }
+    }
+
+    let mut graph = Graph::digraph("day 08");
+    let mut ids: Vec<usize> = (0..network.len()).collect();
+    ids.sort_by_key(|id| network.name(*id));
+    for id in ids {
+        let name = network.name(id);
+        let node = graph.node(name);
+        if name.ends_with('A') {
+            node.attr("shape", "doublecircle").attr("color", "green");
+        } else if name.ends_with('Z') {
+            node.attr("shape", "doublecircle").attr("color", "red");
+        }
 
-        if check_done(cur_key) {
-            return iters;
+        for (right, label) in [(false, "L"), (true, "R")] {
+            // the first instruction of the given direction leads along the same edge.
+            let Some(index) = instructions.iter().position(|r| *r == right) else {
+                continue;
+            };
+            let edge = graph.edge(name, network.name(network.next(id, index)));
+            edge.attr("label", label);
+            if taken.contains(&(id, right)) {
+                edge.attr("color", "blue").attr("penwidth", 2);
+            }
         }
-        ind = (ind + 1) % pattern.len(); // circular indexing
     }
+    graph
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (pattern, maps) = parse_input(input);
-    Some(gen_solve("AAA", pattern, maps, |s| s == "ZZZ"))
+pub fn part_one(input: &str) -> Option<Answer> {
+    let network: Network = input.parse().ok()?;
+    dot::export(|| network_graph(&network));
+    let end = network.id("ZZZ")?;
+    let ghost = network.ghost(network.id("AAA")?, |node| node == end);
+    network::first_common_arrival(&[ghost]).map(Answer::from)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (pattern, maps) = parse_input(input);
-    let cur_keys: Vec<&str> = maps.keys().cloned().filter(|s| s.ends_with('A')).collect();
-    cur_keys
-        .iter()
-        .map(|key| gen_solve(key, pattern.clone(), maps.clone(), |s| s.ends_with('Z')))
-        .reduce(lcm)
+pub fn part_two(input: &str) -> Option<Answer> {
+    let network: Network = input.parse().ok()?;
+    let ghosts: Vec<Ghost> = network
+        .find(|name| name.ends_with('A'))
+        .into_iter()
+        .map(|start| network.ghost(start, |node| network.name(node).ends_with('Z')))
+        .collect();
+    network::first_common_arrival(&ghosts).map(Answer::from)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
 
     use super::*;
 
+    #[test]
+    fn validates_network() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(validate(&input), Ok(()));
+        assert_eq!(
+            validate(&input.replace("ZZZ = (ZZZ, ZZZ)", "ZZZ = (ZZZ, YYY)")),
+            Err(vec!["node `YYY` is never defined".into()])
+        );
+        assert_eq!(
+            validate(&advent_of_code::template::read_file_part(
+                "examples", DAY, 2
+            )),
+            Err(vec![
+                "part one expects the network to contain nodes AAA and ZZZ".into()
+            ])
+        );
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+        assert_eq!(result, Some(Answer::from(6)));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&fs::read_to_string("./data/examples/08_2.txt").unwrap());
-        assert_eq!(result, Some(6));
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ));
+        assert_eq!(result, Some(Answer::from(6)));
     }
 }
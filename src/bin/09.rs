@@ -1,52 +1,37 @@
+use advent_of_code::poly::Newton;
+use advent_of_code::Answer;
 advent_of_code::solution!(9);
 
-fn predict(mut vec: Vec<i32>, next: bool) -> i32 {
-    // if next is true, predict next, else predict previous
-    let mut elems: Vec<i32> = Vec::new();
-    while vec.iter().any(|x| *x != 0) {
-        elems.push(if next {
-            *vec.last().unwrap()
-        } else {
-            *vec.first().unwrap()
-        });
-        vec = vec.windows(2).map(|arr| arr[1] - arr[0]).collect();
-    }
-
-    elems
-        .iter()
-        .rev()
-        .cloned()
-        .reduce(|acc, e| if next { acc + e } else { e - acc })
-        .unwrap()
-}
-
-fn parse_input(input: &str) -> Vec<Vec<i32>> {
+fn parse_input(input: &str) -> Vec<Vec<i128>> {
     input
         .lines()
         .map(|s| {
             s.split(' ')
                 .map(|x| x.parse().unwrap())
-                .collect::<Vec<i32>>()
+                .collect::<Vec<i128>>()
         })
-        .collect::<Vec<Vec<i32>>>()
+        .collect::<Vec<Vec<i128>>>()
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
+pub fn part_one(input: &str) -> Option<Answer> {
     Some(
         parse_input(input)
             .iter()
-            .cloned()
-            .map(|v| predict(v, true))
+            .map(|history| {
+                Newton::from_samples(history)
+                    .map_or_else(Answer::from, |newton| newton.eval(history.len() as i128))
+            })
             .sum(),
     )
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
+pub fn part_two(input: &str) -> Option<Answer> {
     Some(
         parse_input(input)
             .iter()
-            .cloned()
-            .map(|v| predict(v, false))
+            .map(|history| {
+                Newton::from_samples(history).map_or_else(Answer::from, |newton| newton.eval(-1))
+            })
             .sum(),
     )
 }
@@ -58,12 +43,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(114));
+        assert_eq!(result, Some(Answer::from(114)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        assert_eq!(result, Some(Answer::from(2)));
     }
 }
@@ -1,4 +1,44 @@
-advent_of_code::solution!(10);
+use advent_of_code::validate::{self, Validation, Validator};
+use advent_of_code::viz::{self, Frame, Rgb};
+use advent_of_code::Answer;
+use std::collections::HashSet;
+
+advent_of_code::solution!(10, validate: validate);
+
+pub fn validate(input: &str) -> Validation {
+    let mut validator = Validator::new();
+    validator.require(validate::rectangular_grid(input));
+    validator.require(validate::grid_chars(input, "|-LJ7F.S"));
+
+    if let Some((y, x)) = validator.require(validate::find_unique(input, 'S')) {
+        let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let connects = |(dy, dx): (i32, i32), pipes: &str| {
+            let (ny, nx) = (y as i32 + dy, x as i32 + dx);
+            ny >= 0
+                && nx >= 0
+                && grid
+                    .get(ny as usize)
+                    .and_then(|row| row.get(nx as usize))
+                    .is_some_and(|c| pipes.contains(*c))
+        };
+        let n_connections = [
+            connects((-1, 0), "|7F"),
+            connects((1, 0), "|LJ"),
+            connects((0, -1), "-LF"),
+            connects((0, 1), "-J7"),
+        ]
+        .iter()
+        .filter(|c| **c)
+        .count();
+
+        validator.check(
+            n_connections == 2,
+            format!("'S' must connect to exactly two pipes, found {n_connections}"),
+        );
+    }
+
+    validator.finish()
+}
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 enum Pipe {
@@ -51,9 +91,9 @@ fn get_val(grid: &[Vec<Pipe>], (y, x): (i32, i32)) -> Option<Pipe> {
 
 fn get_path(grid: &[Vec<Pipe>]) -> Option<Vec<(i32, i32)>> {
     let mut start = (0, 0);
-    for y in 0..grid.len() {
-        for x in 0..grid[0].len() {
-            if grid[y][x] == Pipe::Start {
+    for (y, row) in grid.iter().enumerate() {
+        for (x, pipe) in row.iter().enumerate() {
+            if *pipe == Pipe::Start {
                 start = (y as i32, x as i32);
                 break;
             }
@@ -93,7 +133,44 @@ fn get_path(grid: &[Vec<Pipe>]) -> Option<Vec<(i32, i32)>> {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+/// Draws the loop with box-drawing characters and marks enclosed tiles found by a scanline.
+fn loop_frame(input: &str, path: &[(i32, i32)]) -> Frame {
+    let mut frame = Frame::from_text("Day 10: loop (yellow) and enclosed tiles (green)", input);
+    let on_loop: HashSet<(i32, i32)> = path.iter().copied().collect();
+
+    // `S` crosses the scanline if the loop enters or leaves it from above.
+    let start = path[0];
+    let start_north = [path[1], path[path.len() - 1]].contains(&(start.0 - 1, start.1));
+
+    for (y, line) in input.lines().enumerate() {
+        let mut inside = false;
+        for (x, c) in line.chars().enumerate() {
+            if on_loop.contains(&(y as i32, x as i32)) {
+                if matches!(c, '|' | 'L' | 'J') || (c == 'S' && start_north) {
+                    inside = !inside;
+                }
+                let (ch, color) = match c {
+                    '|' => ('│', Rgb::YELLOW),
+                    '-' => ('─', Rgb::YELLOW),
+                    'L' => ('└', Rgb::YELLOW),
+                    'J' => ('┘', Rgb::YELLOW),
+                    '7' => ('┐', Rgb::YELLOW),
+                    'F' => ('┌', Rgb::YELLOW),
+                    c => (c, Rgb::RED),
+                };
+                frame.set(y, x, ch, Some(color));
+            } else if inside {
+                frame.set(y, x, 'I', Some(Rgb::GREEN));
+            } else {
+                frame.set(y, x, '.', None);
+            }
+        }
+    }
+
+    frame
+}
+
+pub fn part_one(input: &str) -> Option<Answer> {
     let grid: Vec<Vec<Pipe>> = input
         .lines()
         .map(|line| line.chars().map(Pipe::from_char).collect())
@@ -101,48 +178,54 @@ pub fn part_one(input: &str) -> Option<u32> {
 
     let path = get_path(&grid).unwrap();
 
-    Some(path.len() as u32 / 2)
+    Some(Answer::from(path.len()) / 2)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<Answer> {
     let grid: Vec<Vec<Pipe>> = input
         .lines()
         .map(|line| line.chars().map(Pipe::from_char).collect())
         .collect();
 
     let mut path = get_path(&grid).unwrap();
+    viz::emit(|| loop_frame(input, &path));
 
     path.push(path[0]);
 
-    let area: i32 = path
+    let area: Answer = path
         .windows(2)
-        .map(|arr| arr[1].0 * arr[0].1 - arr[1].1 * arr[0].0)
-        .sum::<i32>()
+        .map(|arr| Answer::from(arr[1].0) * arr[0].1 - Answer::from(arr[1].1) * arr[0].0)
+        .sum::<Answer>()
         / 2;
 
-    Some((area.abs() - (path.len() as i32 - 1) / 2 + 1) as u32)
+    Some(area.abs() - (path.len() - 1) / 2 + 1)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
+        assert_eq!(result, Some(Answer::from(8)));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&fs::read_to_string("./data/examples/10_2.txt").unwrap());
-        assert_eq!(result, Some(4));
-
-        let result = part_two(&fs::read_to_string("./data/examples/10_3.txt").unwrap());
-        assert_eq!(result, Some(8));
-
-        let result = part_two(&fs::read_to_string("./data/examples/10_4.txt").unwrap());
-        assert_eq!(result, Some(10));
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ));
+        assert_eq!(result, Some(Answer::from(4)));
+
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", DAY, 3,
+        ));
+        assert_eq!(result, Some(Answer::from(8)));
+
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", DAY, 4,
+        ));
+        assert_eq!(result, Some(Answer::from(10)));
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::Answer;
 use itertools::{iproduct, Itertools};
 
 advent_of_code::solution!(11);
@@ -28,7 +29,7 @@ fn manhattan_plus(
     xs: &[usize],
     ys: &[usize],
     factor: u64,
-) -> u64 {
+) -> Answer {
     let min_y = std::cmp::min(start.0, end.0);
     let min_x = std::cmp::min(start.1, end.1);
     let max_y = std::cmp::max(start.0, end.0);
@@ -44,10 +45,12 @@ fn manhattan_plus(
             .filter(|y| y > &min_y && y < &max_y)
             .count();
 
-    n_traversed as u64 * (factor - 1) + (max_x - min_x + max_y - min_y) as u64
+    Answer::from(n_traversed) * (factor - 1) + (max_x - min_x + max_y - min_y)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+/// Sums the distances between all pairs of galaxies after every empty row and column has been
+/// replaced by `factor` empty ones.
+fn sum_distances(input: &str, factor: u64) -> Answer {
     let grid: Vec<Vec<bool>> = input
         .lines()
         .map(|line| line.chars().map(|c| c == '#').collect())
@@ -59,49 +62,89 @@ pub fn part_one(input: &str) -> Option<u64> {
 
     let rows_to_add = get_empty_rows(&grid_t);
 
-    Some(
-        get_galaxy_locs(&grid)
-            .iter()
-            .combinations(2)
-            .map(|v| manhattan_plus(*v[0], *v[1], &rows_to_add, &cols_to_add, 2))
-            .sum(),
-    )
+    get_galaxy_locs(&grid)
+        .iter()
+        .combinations(2)
+        .map(|v| manhattan_plus(*v[0], *v[1], &rows_to_add, &cols_to_add, factor))
+        .sum()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let grid: Vec<Vec<bool>> = input
-        .lines()
-        .map(|line| line.chars().map(|c| c == '#').collect())
-        .collect();
-
-    let grid_t = transpose_grid(&grid);
-
-    let cols_to_add = get_empty_rows(&grid);
-
-    let rows_to_add = get_empty_rows(&grid_t);
+pub fn part_one(input: &str) -> Option<Answer> {
+    Some(sum_distances(input, 2))
+}
 
-    Some(
-        get_galaxy_locs(&grid)
-            .iter()
-            .combinations(2)
-            .map(|v| manhattan_plus(*v[0], *v[1], &rows_to_add, &cols_to_add, 1000000))
-            .sum(),
-    )
+pub fn part_two(input: &str) -> Option<Answer> {
+    Some(sum_distances(input, 1000000))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::{self, Differential};
+
+    /// Copies every empty row and column `factor - 1` times and measures the distances on the
+    /// expanded image.
+    fn expand_image(input: &str, factor: usize) -> u64 {
+        let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let mut rows: Vec<Vec<char>> = Vec::new();
+        for row in &grid {
+            let copies = if row.contains(&'#') { 1 } else { factor };
+            rows.extend(std::iter::repeat_n(row.clone(), copies));
+        }
+
+        let mut expanded: Vec<Vec<char>> = vec![Vec::new(); rows.len()];
+        for col in 0..grid[0].len() {
+            let empty = grid.iter().all(|row| row[col] != '#');
+            for _ in 0..if empty { factor } else { 1 } {
+                for (line, row) in expanded.iter_mut().zip(&rows) {
+                    line.push(row[col]);
+                }
+            }
+        }
+
+        let galaxies: Vec<(usize, usize)> = iproduct!(0..expanded.len(), 0..expanded[0].len())
+            .filter(|&(row, col)| expanded[row][col] == '#')
+            .collect();
+        let mut total = 0;
+        for (i, a) in galaxies.iter().enumerate() {
+            for b in &galaxies[i + 1..] {
+                total += (a.0.abs_diff(b.0) + a.1.abs_diff(b.1)) as u64;
+            }
+        }
+        total
+    }
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(374));
+        assert_eq!(result, Some(Answer::from(374)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(82000210));
+        assert_eq!(result, Some(Answer::from(82000210)));
+    }
+
+    #[test]
+    fn test_expansion_against_oracle() {
+        for factor in [2, 3, 10] {
+            Differential::new(
+                DAY,
+                ("sum_distances", move |input: &str| {
+                    sum_distances(input, factor as u64)
+                }),
+                ("expand_image", move |input: &str| {
+                    Answer::from(expand_image(input, factor))
+                }),
+            )
+            .sizes(1..=15)
+            .shrink(|input| {
+                let mut smaller = differential::shrink_grid(input);
+                smaller.extend(differential::blank_cells(input, '.'));
+                smaller
+            })
+            .run();
+        }
     }
 }
@@ -1,5 +1,14 @@
+use advent_of_code::Answer;
 use std::collections::HashMap;
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, compare: [1: part_one => refactor::part_one]);
+
+// the refactored solution is shared with the `12_refactor` binary, it is included here to compare
+// it via `--compare`.
+#[allow(dead_code)]
+#[path = "shared/12_refactor.rs"]
+mod refactor;
+
+type Memo = HashMap<(bool, bool, Vec<char>, Vec<usize>), u64>;
 
 fn get_counts(v: &Vec<char>) -> Vec<usize> {
     let mut ans: Vec<usize> = Vec::new();
@@ -19,7 +28,7 @@ fn get_counts(v: &Vec<char>) -> Vec<usize> {
     ans
 }
 
-fn check_final(v: &Vec<char>, pat: &Vec<usize>) -> bool {
+fn check_final(v: &Vec<char>, pat: &[usize]) -> bool {
     let ans = get_counts(v);
     if ans.len() != pat.len() {
         return false;
@@ -27,7 +36,7 @@ fn check_final(v: &Vec<char>, pat: &Vec<usize>) -> bool {
     ans.iter().zip(pat.iter()).all(|(a, b)| a == b)
 }
 
-fn check(v: &Vec<char>, pat: &Vec<usize>, n_chars: usize) -> bool {
+fn check(v: &Vec<char>, pat: &[usize], n_chars: usize) -> bool {
     let ans = get_counts(v);
 
     let mut last_neq = false; // whether we've already encountered a neq
@@ -73,12 +82,7 @@ fn is_partial(v: &Vec<char>, pat: &[usize]) -> bool {
     pat.iter().zip(ans.iter()).any(|(a, b)| b < a)
 }
 
-fn get_next(
-    remaining: &[char],
-    builder: Vec<char>,
-    pattern: &Vec<usize>,
-    memo: &mut HashMap<(bool, bool, Vec<char>, Vec<usize>), u64>,
-) -> u64 {
+fn get_next(remaining: &[char], builder: Vec<char>, pattern: &Vec<usize>, memo: &mut Memo) -> u64 {
     if !check(&builder, pattern, remaining.len()) {
         return 0;
     }
@@ -125,7 +129,7 @@ fn get_next(
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(input: &str) -> Option<Answer> {
     let patterns: Vec<Vec<usize>> = input
         .lines()
         .map(|line| {
@@ -138,7 +142,7 @@ pub fn part_one(input: &str) -> Option<u64> {
         })
         .collect();
 
-    let mut memo: HashMap<(bool, bool, Vec<char>, Vec<usize>), u64> = HashMap::new();
+    let mut memo: Memo = HashMap::new();
     Some(
         input
             .lines()
@@ -151,7 +155,7 @@ pub fn part_one(input: &str) -> Option<u64> {
             })
             .zip(patterns.iter())
             .map(|(in_vec, pattern)| get_next(&in_vec, Vec::new(), pattern, &mut memo))
-            .sum::<u64>(),
+            .sum(),
     )
 }
 
@@ -167,7 +171,7 @@ fn dup_chars(in_vec: &[char]) -> Vec<char> {
     new_vec
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str) -> Option<Answer> {
     let patterns: Vec<Vec<usize>> = input
         .lines()
         .map(|line| {
@@ -187,7 +191,7 @@ pub fn part_two(input: &str) -> Option<u64> {
             new_vec
         })
         .collect::<Vec<Vec<usize>>>();
-    let mut memo: HashMap<(bool, bool, Vec<char>, Vec<usize>), u64> = HashMap::new();
+    let mut memo: Memo = HashMap::new();
     Some(
         input
             .lines()
@@ -213,13 +217,13 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
+        assert_eq!(result, Some(Answer::from(21)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(525152));
+        assert_eq!(result, Some(Answer::from(525152)));
     }
 
     // #[test]
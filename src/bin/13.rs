@@ -1,3 +1,4 @@
+use advent_of_code::Answer;
 advent_of_code::solution!(13);
 
 fn transpose_grid(grid: &[Vec<u8>]) -> Vec<Vec<u8>> {
@@ -41,11 +42,11 @@ pub fn get_pattern_vals(input: &str, allowable_diff: u8) -> u32 {
     100 * find_changes(&grid, allowable_diff) + find_changes(&transpose_grid(&grid), allowable_diff)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<Answer> {
     Some(input.split("\n\n").map(|p| get_pattern_vals(p, 0)).sum())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<Answer> {
     Some(input.split("\n\n").map(|p| get_pattern_vals(p, 1)).sum())
 }
 
@@ -56,12 +57,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(405));
+        assert_eq!(result, Some(Answer::from(405)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(400));
+        assert_eq!(result, Some(Answer::from(400)));
     }
 }
@@ -1,8 +1,10 @@
-use itertools::iproduct;
+use advent_of_code::debugger::Simulation;
+use advent_of_code::viz::{self, Frame, Rgb};
+use advent_of_code::Answer;
 use std::collections::HashMap;
-advent_of_code::solution!(14);
+advent_of_code::solution!(14, step: simulation);
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 enum Direction {
     North,
     South,
@@ -10,180 +12,239 @@ enum Direction {
     West,
 }
 
-impl Direction {
-    fn get_iter(&self, dimensions: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
-        match self {
-            Direction::North | Direction::West => iproduct!(
-                (0..dimensions.0).collect::<Vec<usize>>(),
-                (0..dimensions.1).collect::<Vec<usize>>()
-            ),
-            Direction::East => iproduct!(
-                (0..dimensions.0).collect::<Vec<usize>>(),
-                (0..dimensions.1).rev().collect::<Vec<usize>>()
-            ),
-            Direction::South => iproduct!(
-                (0..dimensions.0).rev().collect::<Vec<usize>>(),
-                (0..dimensions.1).collect::<Vec<usize>>()
-            ),
-        }
+/// Order of the tilts in a spin cycle.
+const SPIN_CYCLE: [Direction; 4] = [
+    Direction::North,
+    Direction::West,
+    Direction::South,
+    Direction::East,
+];
+
+/// A mask of the lowest `n` bits.
+fn low_bits(n: u32) -> u128 {
+    u128::MAX.checked_shr(128 - n).unwrap_or(0)
+}
+
+/// Splits the free tiles of a line of `len` tiles into the runs between cube rocks.
+fn segments(cubes: u128, len: usize) -> Vec<u128> {
+    let mut free = low_bits(len as u32) & !cubes;
+    let mut segments = Vec::new();
+    while free != 0 {
+        let start = free.trailing_zeros();
+        let run = low_bits((!(free >> start)).trailing_zeros()) << start;
+        segments.push(run);
+        free &= !run;
     }
+    segments
+}
 
-    fn move_anti_gravity(&self, ind: usize) -> usize {
-        match self {
-            Direction::North | Direction::West => ind + 1,
-            _ => {
-                if ind > 0 {
-                    ind - 1
-                } else {
-                    ind
-                }
-            }
+/// Rolls the rounded rocks of each line to the low or the high end of the run they are in.
+fn slide(lines: &mut [u128], segments: &[Vec<u128>], toward_low: bool) {
+    for (line, segments) in lines.iter_mut().zip(segments) {
+        let mut packed = 0;
+        for segment in segments {
+            let rocks = (*line & segment).count_ones();
+            let shift = if toward_low {
+                segment.trailing_zeros()
+            } else {
+                128 - segment.leading_zeros() - rocks
+            };
+            packed |= low_bits(rocks) << shift;
         }
+        *line = packed;
     }
+}
 
-    fn get_bottom(&self, dimensions: (usize, usize)) -> usize {
-        match self {
-            Direction::North => 0,
-            Direction::South => dimensions.0 - 1,
-            Direction::East => dimensions.1 - 1,
-            Direction::West => 0,
+/// Swaps rows and columns, `len` is the number of lines of the result.
+fn transpose(lines: &[u128], len: usize) -> Vec<u128> {
+    let mut transposed = vec![0; len];
+    for (i, line) in lines.iter().enumerate() {
+        let mut bits = *line;
+        while bits != 0 {
+            transposed[bits.trailing_zeros() as usize] |= 1 << i;
+            bits &= bits - 1;
         }
     }
+    transposed
 }
 
-fn tilt(
-    grid: &mut HashMap<(usize, usize), char>,
-    direction: Direction,
-    dimensions: (usize, usize),
-) {
-    let mut next_loc_map: HashMap<usize, usize> = HashMap::new();
-    let bottom_ind = direction.get_bottom(dimensions);
-    for (row, col) in direction.get_iter(dimensions) {
-        match grid.get(&(row, col)).unwrap() {
-            '.' => {}
-            '#' => {
-                if direction == Direction::West || direction == Direction::East {
-                    next_loc_map.insert(row, direction.move_anti_gravity(col));
-                } else {
-                    next_loc_map.insert(col, direction.move_anti_gravity(row));
-                }
-            }
-            'O' => {
-                if direction == Direction::West || direction == Direction::East {
-                    let new_col = next_loc_map.get(&row).unwrap_or(&bottom_ind);
-
-                    if col != *new_col {
-                        grid.insert((row, *new_col), 'O');
-                        grid.insert((row, col), '.');
-                    }
-                    next_loc_map.insert(row, direction.move_anti_gravity(*new_col));
-                } else {
-                    let new_row = next_loc_map.get(&col).unwrap_or(&bottom_ind);
+/// The platform as bitboards, bit `col` of a row mask is the tile at `(row, col)`. Rounded rocks
+/// are stored by row, cube rocks never move and are kept as the free runs of every row and column.
+#[derive(Clone)]
+struct Platform {
+    height: usize,
+    width: usize,
+    rounded: Vec<u128>,
+    cubes: Vec<u128>,
+    row_segments: Vec<Vec<u128>>,
+    col_segments: Vec<Vec<u128>>,
+}
 
-                    if row != *new_row {
-                        grid.insert((*new_row, col), 'O');
-                        grid.insert((row, col), '.');
-                    }
-                    next_loc_map.insert(col, direction.move_anti_gravity(*new_row));
-                }
-            }
-            _ => {
-                panic!("wrong char in grid");
-            }
+impl Platform {
+    fn parse(input: &str) -> Self {
+        let rows: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        let (height, width) = (rows.len(), rows[0].len());
+        assert!(
+            height <= 128 && width <= 128,
+            "platform does not fit a u128"
+        );
+
+        let mask = |tile: u8| -> Vec<u128> {
+            rows.iter()
+                .map(|row| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|(_, c)| **c == tile)
+                        .fold(0, |mask, (col, _)| mask | 1 << col)
+                })
+                .collect()
+        };
+        let cubes = mask(b'#');
+        let row_segments = cubes.iter().map(|row| segments(*row, width)).collect();
+        let col_segments = transpose(&cubes, width)
+            .iter()
+            .map(|col| segments(*col, height))
+            .collect();
+
+        Platform {
+            height,
+            width,
+            rounded: mask(b'O'),
+            cubes,
+            row_segments,
+            col_segments,
         }
     }
-}
 
-fn compute_north_load(grid: &HashMap<(usize, usize), char>, dimensions: (usize, usize)) -> u32 {
-    let mut acc = 0;
-    for row in 0..dimensions.0 {
-        for col in 0..dimensions.1 {
-            if grid.get(&(row, col)).unwrap() == &'O' {
-                acc += dimensions.0 - row;
+    fn tilt(&mut self, direction: Direction) {
+        match direction {
+            Direction::West => slide(&mut self.rounded, &self.row_segments, true),
+            Direction::East => slide(&mut self.rounded, &self.row_segments, false),
+            Direction::North | Direction::South => {
+                let mut cols = transpose(&self.rounded, self.width);
+                slide(&mut cols, &self.col_segments, direction == Direction::North);
+                self.rounded = transpose(&cols, self.height);
             }
         }
     }
-    acc as u32
-}
 
-fn _disp_grid(grid: &HashMap<(usize, usize), char>, dimensions: (usize, usize)) {
-    for x in 0..dimensions.0 {
-        for y in 0..dimensions.1 {
-            print!("{}", grid.get(&(x, y)).unwrap());
+    fn north_load(&self) -> usize {
+        self.rounded
+            .iter()
+            .enumerate()
+            .map(|(row, rocks)| rocks.count_ones() as usize * (self.height - row))
+            .sum()
+    }
+
+    fn frame(&self, title: String) -> Frame {
+        let mut frame = Frame::new(title, self.width, self.height);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.rounded[row] >> col & 1 == 1 {
+                    frame.set(row, col, 'O', Some(Rgb::WHITE));
+                } else if self.cubes[row] >> col & 1 == 1 {
+                    frame.set(row, col, '#', Some(Rgb::BLUE));
+                } else {
+                    frame.set(row, col, '.', None);
+                }
+            }
         }
-        println!();
+        frame
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let grid = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>();
+/// Steps through the spin cycles one tilt at a time until the rocks return to an earlier
+/// arrangement after a full cycle.
+#[derive(Clone)]
+pub struct SpinCycles {
+    platform: Platform,
+    tilts: usize,
+    /// Packed rock positions after each completed spin cycle, mapped to the cycle number.
+    seen: HashMap<Vec<u128>, usize>,
+    repeats: Option<(usize, usize)>,
+}
+
+impl Simulation for SpinCycles {
+    fn step(&mut self) -> bool {
+        self.platform.tilt(SPIN_CYCLE[self.tilts % 4]);
+        self.tilts += 1;
 
-    let mut grid_map: HashMap<(usize, usize), char> = HashMap::new();
-    for row in 0..grid.len() {
-        for col in 0..grid[0].len() {
-            grid_map.insert((row, col), grid[row][col]);
+        if self.tilts.is_multiple_of(4) {
+            let cycle = self.tilts / 4;
+            if let Some(first) = self.seen.insert(self.platform.rounded.clone(), cycle) {
+                self.repeats = Some((first, cycle - first));
+            }
         }
+        self.repeats.is_none()
     }
-    let dimensions = (grid.len(), grid[0].len());
-    tilt(&mut grid_map, Direction::North, dimensions);
-    // disp_grid(&grid_map, dimensions);
-    Some(compute_north_load(&grid_map, dimensions))
-}
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let grid = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>();
+    fn frame(&self) -> Frame {
+        let title = match self.tilts {
+            0 => "Day 14: initial platform".to_string(),
+            n => format!(
+                "Day 14: spin cycle {}, tilted {:?}",
+                (n - 1) / 4 + 1,
+                SPIN_CYCLE[(n - 1) % 4]
+            ),
+        };
+        self.platform.frame(title)
+    }
 
-    let mut grid_map: HashMap<(usize, usize), char> = HashMap::new();
-    for row in 0..grid.len() {
-        for col in 0..grid[0].len() {
-            grid_map.insert((row, col), grid[row][col]);
+    fn state(&self) -> Vec<(&'static str, String)> {
+        let mut state = vec![
+            ("completed spin cycles", (self.tilts / 4).to_string()),
+            ("next tilt", format!("{:?}", SPIN_CYCLE[self.tilts % 4])),
+            ("north load", self.platform.north_load().to_string()),
+        ];
+        if let Some((first, len)) = self.repeats {
+            state.push((
+                "repeats",
+                format!("arrangement after cycle {first} repeats every {len} cycles"),
+            ));
         }
+        state
+    }
+}
+
+pub fn simulation(input: &str) -> SpinCycles {
+    SpinCycles {
+        platform: Platform::parse(input),
+        tilts: 0,
+        seen: HashMap::new(),
+        repeats: None,
     }
+}
 
-    let dimensions = (grid.len(), grid[0].len());
+pub fn part_one(input: &str) -> Option<Answer> {
+    let mut platform = Platform::parse(input);
+    platform.tilt(Direction::North);
+    viz::emit(|| platform.frame("Day 14: tilted north".into()));
+    Some(platform.north_load().into())
+}
 
-    let mut loads: Vec<u32> = Vec::new();
+pub fn part_two(input: &str) -> Option<Answer> {
     const TOTAL_CYCLES: usize = 1000000000;
-    let n_cycles = 500;
-    let warm_up = 150;
-    for _ in 0..n_cycles {
-        for dir in vec![
-            Direction::North,
-            Direction::West,
-            Direction::South,
-            Direction::East,
-        ] {
-            tilt(&mut grid_map, dir, dimensions);
+    let mut platform = Platform::parse(input);
+
+    // loads[n] is the load after n spin cycles.
+    let mut loads = vec![platform.north_load()];
+    let mut seen: HashMap<Vec<u128>, usize> = HashMap::from([(platform.rounded.clone(), 0)]);
+    for cycle in 1..=TOTAL_CYCLES {
+        for dir in SPIN_CYCLE {
+            platform.tilt(dir);
+            viz::emit(|| {
+                let title = format!("Day 14: spin cycle {cycle}, tilted {dir:?}");
+                platform.frame(title)
+            });
         }
-        loads.push(compute_north_load(&grid_map, dimensions));
-    }
+        loads.push(platform.north_load());
 
-    let max_val = loads.iter().skip(warm_up).max().unwrap();
-
-    let _cycle_len = loads
-        .iter()
-        .cloned()
-        .enumerate()
-        .filter(|(_, b)| b == max_val)
-        .map(|(a, _)| a)
-        .collect::<Vec<usize>>()
-        .windows(2)
-        .map(|arr| arr[1] - arr[0])
-        .next()
-        .unwrap();
-
-    let cycle_len = 7;
-    loads
-        .iter()
-        .skip(warm_up)
-        .nth((TOTAL_CYCLES - warm_up - 1) % cycle_len)
-        .copied()
+        if let Some(first) = seen.insert(platform.rounded.clone(), cycle) {
+            let period = cycle - first;
+            return Some(loads[first + (TOTAL_CYCLES - first) % period].into());
+        }
+    }
+    loads.last().copied().map(Answer::from)
 }
 
 #[cfg(test)]
@@ -193,12 +254,33 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(136));
+        assert_eq!(result, Some(Answer::from(136)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(64));
+        assert_eq!(result, Some(Answer::from(64)));
+    }
+
+    #[test]
+    fn test_spin_cycle() {
+        let mut platform = Platform::parse(&advent_of_code::template::read_file("examples", DAY));
+        for dir in SPIN_CYCLE {
+            platform.tilt(dir);
+        }
+        let expected = Platform::parse(
+            ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....",
+        );
+        assert_eq!(platform.rounded, expected.rounded);
     }
 }
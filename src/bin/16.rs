@@ -1,6 +1,29 @@
-use std::collections::{HashSet, VecDeque};
+use advent_of_code::debugger::Simulation;
+use advent_of_code::validate::{self, Validation, Validator};
+use advent_of_code::viz::{self, Frame, Rgb};
+use advent_of_code::Answer;
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::{DiGraph, NodeIndex};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::rc::Rc;
+use std::thread;
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, validate: validate, step: simulation);
+
+pub fn validate(input: &str) -> Validation {
+    let mut validator = Validator::new();
+    validator.require(validate::grid_chars(input, ".|-/\\"));
+
+    if let Some((rows, cols)) = validator.require(validate::rectangular_grid(input)) {
+        // `LightDir::get_next` computes the next location in `i8`.
+        validator.check(
+            rows <= i8::MAX as usize && cols <= i8::MAX as usize,
+            format!("grid must not exceed 127x127 tiles, got {rows}x{cols}"),
+        );
+    }
+
+    validator.finish()
+}
 
 #[derive(PartialEq, Debug, Clone, Copy, Eq, Hash)]
 enum LightDir {
@@ -20,7 +43,7 @@ impl LightDir {
         }
     }
 
-    fn from_vel(&self, vel: (i8, i8)) -> Self {
+    fn with_vel(&self, vel: (i8, i8)) -> Self {
         match vel {
             (-1, 0) => LightDir::N,
             (0, 1) => LightDir::E,
@@ -34,18 +57,18 @@ impl LightDir {
         let vel = self.vel();
         let mut to_split = false;
         let new_dir = match c {
-            '/' => self.from_vel((-vel.1, -vel.0)),
-            '\\' => self.from_vel((vel.1, vel.0)),
+            '/' => self.with_vel((-vel.1, -vel.0)),
+            '\\' => self.with_vel((vel.1, vel.0)),
 
             '-' => {
-                let temp = self.from_vel((0, vel.0));
+                let temp = self.with_vel((0, vel.0));
                 if temp != *self {
                     to_split = true;
                 }
                 temp
             }
             '|' => {
-                let temp = self.from_vel((vel.1, 0));
+                let temp = self.with_vel((vel.1, 0));
                 if temp != *self {
                     to_split = true;
                 }
@@ -76,49 +99,294 @@ impl LightDir {
     }
 }
 
-fn energize(grid: &[Vec<char>], start_state: ((usize, usize), LightDir)) -> u32 {
-    let dimensions = (grid.len() - 1, grid[0].len() - 1);
-    let mut visited: HashSet<((usize, usize), LightDir)> = HashSet::new();
-    let mut cur_lights: VecDeque<((usize, usize), LightDir)> = VecDeque::new();
-    cur_lights.push_front(start_state);
+/// Beams that still have to be followed, advanced one tile at a time.
+#[derive(Clone)]
+struct Beams {
+    visited: HashSet<((usize, usize), LightDir)>,
+    cur_lights: VecDeque<((usize, usize), LightDir)>,
+}
+
+impl Beams {
+    /// A beam entering the grid at `loc` heading `dir`, split in two if the tile is a splitter.
+    fn new(grid: &[Vec<char>], (loc, dir): ((usize, usize), LightDir)) -> Self {
+        let (start_dir, split) = dir.transition_lens(grid[loc.0][loc.1]);
+        let mut cur_lights = VecDeque::from([(loc, start_dir)]);
+        if split {
+            let vel = start_dir.vel();
+            cur_lights.push_back((loc, start_dir.with_vel((-vel.0, -vel.1))));
+        }
+        Beams {
+            visited: HashSet::new(),
+            cur_lights,
+        }
+    }
+
+    /// Moves the next queued beam by one tile. Returns `false` once no beams are left.
+    fn advance(&mut self, grid: &[Vec<char>]) -> bool {
+        let dimensions = (grid.len() - 1, grid[0].len() - 1);
+        let Some((loc, dir)) = self.cur_lights.pop_front() else {
+            return false;
+        };
 
-    while let Some((loc, dir)) = cur_lights.pop_front() {
-        visited.insert((loc, dir));
+        self.visited.insert((loc, dir));
         if let Some(next_loc) = dir.get_next(loc, dimensions) {
             let (next_dir, split) = dir.transition_lens(grid[next_loc.0][next_loc.1]);
-            if !visited.contains(&(next_loc, next_dir)) {
-                cur_lights.push_back((next_loc, next_dir));
+            if !self.visited.contains(&(next_loc, next_dir)) {
+                self.cur_lights.push_back((next_loc, next_dir));
             }
             if split {
                 let vel = next_dir.vel();
-                cur_lights.push_back((next_loc, next_dir.from_vel((-vel.0, -vel.1))));
+                self.cur_lights
+                    .push_back((next_loc, next_dir.with_vel((-vel.0, -vel.1))));
+            }
+        }
+
+        !self.cur_lights.is_empty()
+    }
+}
+
+fn beam_states(
+    grid: &[Vec<char>],
+    start_state: ((usize, usize), LightDir),
+) -> HashSet<((usize, usize), LightDir)> {
+    let mut beams = Beams::new(grid, start_state);
+    while beams.advance(grid) {}
+    beams.visited
+}
+
+/// A set of tiles, tile `(row, col)` is bit `row * cols + col`.
+#[derive(Clone)]
+struct TileSet {
+    words: Vec<u64>,
+}
+
+impl TileSet {
+    fn new(tiles: usize) -> Self {
+        TileSet {
+            words: vec![0; tiles.div_ceil(64)],
+        }
+    }
+
+    fn insert(&mut self, tile: usize) {
+        self.words[tile / 64] |= 1 << (tile % 64);
+    }
+
+    fn union_with(&mut self, other: &TileSet) {
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+/// The paths of beams between mirrors and splitters. A node is a beam hitting a non-empty tile
+/// from a direction, its edges are the straight segments to the next non-empty tiles. Beams caught
+/// in a loop form a strongly connected component, so the tiles energized from a node are the tiles
+/// of its component and of all components reachable from it, computed once for every start.
+struct SegmentGraph {
+    cols: usize,
+    nodes: HashMap<((usize, usize), LightDir), usize>,
+    /// Component of each node, indexing `energized`.
+    components: Vec<usize>,
+    energized: Vec<TileSet>,
+}
+
+impl SegmentGraph {
+    fn new(grid: &[Vec<char>]) -> Self {
+        let (rows, cols) = (grid.len(), grid[0].len());
+        let dimensions = (rows - 1, cols - 1);
+        let dirs = [LightDir::N, LightDir::E, LightDir::S, LightDir::W];
+
+        let mut nodes = HashMap::new();
+        for (row, line) in grid.iter().enumerate() {
+            for (col, c) in line.iter().enumerate() {
+                if *c != '.' {
+                    for dir in dirs {
+                        let id = nodes.len();
+                        nodes.insert(((row, col), dir), id);
+                    }
+                }
+            }
+        }
+
+        // tiles covered by the beams leaving each node, up to the next node.
+        let mut segments: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        let mut graph: DiGraph<(), ()> = DiGraph::with_capacity(nodes.len(), 0);
+        for _ in 0..nodes.len() {
+            graph.add_node(());
+        }
+        for (&(loc, dir), &id) in &nodes {
+            let (out, split) = dir.transition_lens(grid[loc.0][loc.1]);
+            let vel = out.vel();
+            let opposite = split.then(|| out.with_vel((-vel.0, -vel.1)));
+
+            segments[id].push(loc.0 * cols + loc.1);
+            for out in [Some(out), opposite].into_iter().flatten() {
+                let mut cur = loc;
+                while let Some(next) = out.get_next(cur, dimensions) {
+                    if let Some(target) = nodes.get(&(next, out)) {
+                        graph.add_edge(NodeIndex::new(id), NodeIndex::new(*target), ());
+                        break;
+                    }
+                    segments[id].push(next.0 * cols + next.1);
+                    cur = next;
+                }
+            }
+        }
+
+        // components come in reverse topological order, so successors are always done first.
+        let sccs = tarjan_scc(&graph);
+        let mut components = vec![0; nodes.len()];
+        for (component, scc) in sccs.iter().enumerate() {
+            for node in scc {
+                components[node.index()] = component;
+            }
+        }
+        let mut energized: Vec<TileSet> = Vec::with_capacity(sccs.len());
+        for (component, scc) in sccs.iter().enumerate() {
+            let mut tiles = TileSet::new(rows * cols);
+            for node in scc {
+                for tile in &segments[node.index()] {
+                    tiles.insert(*tile);
+                }
+                for next in graph.neighbors(*node) {
+                    let next = components[next.index()];
+                    if next != component {
+                        tiles.union_with(&energized[next]);
+                    }
+                }
+            }
+            energized.push(tiles);
+        }
+
+        SegmentGraph {
+            cols,
+            nodes,
+            components,
+            energized,
+        }
+    }
+
+    /// Number of tiles energized by a beam entering the grid at `loc` heading `dir`.
+    fn energize(&self, grid: &[Vec<char>], (loc, dir): ((usize, usize), LightDir)) -> usize {
+        let dimensions = (grid.len() - 1, self.cols - 1);
+        let mut tiles = TileSet::new(grid.len() * self.cols);
+        let mut cur = Some(loc);
+        while let Some(loc) = cur {
+            if let Some(node) = self.nodes.get(&(loc, dir)) {
+                tiles.union_with(&self.energized[self.components[*node]]);
+                break;
             }
+            tiles.insert(loc.0 * self.cols + loc.1);
+            cur = dir.get_next(loc, dimensions);
+        }
+        tiles.len()
+    }
+}
+
+/// Draws energized tiles, empty tiles show the beam direction or the number of crossing beams.
+fn beam_frame(
+    title: &str,
+    grid: &[Vec<char>],
+    visited: &HashSet<((usize, usize), LightDir)>,
+) -> Frame {
+    let mut frame = Frame::new(title, grid[0].len(), grid.len());
+    let mut beams: HashMap<(usize, usize), Vec<LightDir>> = HashMap::new();
+    for (loc, dir) in visited {
+        beams.entry(*loc).or_default().push(*dir);
+    }
+
+    for (row, line) in grid.iter().enumerate() {
+        for (col, &c) in line.iter().enumerate() {
+            match beams.get(&(row, col)) {
+                Some(dirs) if c == '.' => {
+                    let ch = match dirs.as_slice() {
+                        [LightDir::N] => '^',
+                        [LightDir::E] => '>',
+                        [LightDir::S] => 'v',
+                        [LightDir::W] => '<',
+                        dirs => char::from_digit(dirs.len() as u32, 10).unwrap(),
+                    };
+                    frame.set(row, col, ch, Some(Rgb::YELLOW));
+                }
+                Some(_) => frame.set(row, col, c, Some(Rgb::RED)),
+                None => frame.set(row, col, c, None),
+            }
+        }
+    }
+
+    frame
+}
+
+/// Follows the beams of part one tile by tile.
+#[derive(Clone)]
+pub struct BeamPropagation {
+    grid: Rc<Vec<Vec<char>>>,
+    beams: Beams,
+    steps: usize,
+}
+
+impl Simulation for BeamPropagation {
+    fn step(&mut self) -> bool {
+        self.steps += 1;
+        self.beams.advance(&self.grid)
+    }
+
+    fn frame(&self) -> Frame {
+        let title = format!("Day 16: beams after {} moves", self.steps);
+        let mut frame = beam_frame(&title, &self.grid, &self.beams.visited);
+        for ((row, col), _) in &self.beams.cur_lights {
+            frame.highlight(*row, *col, Rgb::GREEN);
         }
+        frame
     }
 
-    visited
-        .iter()
-        .map(|(loc, _)| loc)
-        .cloned()
-        .collect::<HashSet<(usize, usize)>>()
-        .len() as u32
+    fn state(&self) -> Vec<(&'static str, String)> {
+        let energized: HashSet<&(usize, usize)> =
+            self.beams.visited.iter().map(|(loc, _)| loc).collect();
+        let mut state = vec![
+            ("energized tiles", energized.len().to_string()),
+            ("queued beams", self.beams.cur_lights.len().to_string()),
+        ];
+        if let Some((loc, dir)) = self.beams.cur_lights.front() {
+            state.push(("next beam", format!("{loc:?} heading {dir:?}")));
+        }
+        state
+    }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn simulation(input: &str) -> BeamPropagation {
     let grid: Vec<Vec<char>> = input
         .lines()
         .map(|line| line.chars().collect::<Vec<char>>())
         .collect();
+    BeamPropagation {
+        beams: Beams::new(&grid, ((0, 0), LightDir::E)),
+        grid: Rc::new(grid),
+        steps: 0,
+    }
+}
 
-    let start_loc = (0, 0);
-    let start_dir = LightDir::E
-        .transition_lens(grid[start_loc.0][start_loc.1])
-        .0;
+pub fn part_one(input: &str) -> Option<Answer> {
+    let grid: Vec<Vec<char>> = input
+        .lines()
+        .map(|line| line.chars().collect::<Vec<char>>())
+        .collect();
 
-    Some(energize(&grid, (start_loc, start_dir)))
+    // a single start is quicker to follow tile by tile than to build the segment graph for.
+    let visited = beam_states(&grid, ((0, 0), LightDir::E));
+    viz::emit(|| beam_frame("Day 16: energized tiles", &grid, &visited));
+    let energized: HashSet<(usize, usize)> = visited.iter().map(|(loc, _)| *loc).collect();
+    Some(energized.len().into())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<Answer> {
     let grid: Vec<Vec<char>> = input
         .lines()
         .map(|line| line.chars().collect::<Vec<char>>())
@@ -137,19 +405,29 @@ pub fn part_two(input: &str) -> Option<u32> {
         start_vec.push(((row, 0), LightDir::E));
         start_vec.push(((row, max_col), LightDir::W));
     }
-    start_vec
-        .iter()
-        .cloned()
-        .map(|(start_pos, start_dir)| {
-            energize(
-                &grid,
-                (
-                    start_pos,
-                    start_dir.transition_lens(grid[start_pos.0][start_pos.1]).0,
-                ),
-            )
-        })
-        .max()
+
+    let segments = SegmentGraph::new(&grid);
+    let threads = thread::available_parallelism().map_or(1, usize::from);
+    let chunk_size = start_vec.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let handles: Vec<_> = start_vec
+            .chunks(chunk_size)
+            .map(|starts| {
+                let (grid, segments) = (&grid, &segments);
+                scope.spawn(move || {
+                    starts
+                        .iter()
+                        .map(|start| segments.energize(grid, *start))
+                        .max()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().unwrap())
+            .max()
+    })
+    .map(Answer::from)
 }
 
 #[cfg(test)]
@@ -159,12 +437,41 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
+        assert_eq!(result, Some(Answer::from(46)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(51));
+        assert_eq!(result, Some(Answer::from(51)));
+    }
+
+    /// Energized tiles from following the beams tile by tile.
+    fn simulate(grid: &[Vec<char>], start: ((usize, usize), LightDir)) -> usize {
+        beam_states(grid, start)
+            .iter()
+            .map(|(loc, _)| *loc)
+            .collect::<HashSet<(usize, usize)>>()
+            .len()
+    }
+
+    #[test]
+    fn test_segment_graph() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let segments = SegmentGraph::new(&grid);
+        let (rows, cols) = (grid.len(), grid[0].len());
+        let starts = (0..cols)
+            .flat_map(|col| [((0, col), LightDir::S), ((rows - 1, col), LightDir::N)])
+            .chain(
+                (0..rows).flat_map(|row| [((row, 0), LightDir::E), ((row, cols - 1), LightDir::W)]),
+            );
+        for start in starts {
+            assert_eq!(
+                segments.energize(&grid, start),
+                simulate(&grid, start),
+                "{start:?}"
+            );
+        }
     }
 }
@@ -1,24 +1,39 @@
+use advent_of_code::viz::{self, Frame, Rgb};
+use advent_of_code::Answer;
 use std::collections::HashSet;
 
 advent_of_code::solution!(18);
 
-fn _print_grid(path: &[(i32, i32)]) {
-    let max_y = path.iter().map(|(x, _)| x).max().unwrap();
-    let max_x = path.iter().map(|(_, y)| y).max().unwrap();
-    let path_set: HashSet<(i32, i32)> = path.iter().cloned().collect();
-    for i in 0..=*max_y {
-        for j in 0..=*max_x {
-            if path_set.contains(&(i, j)) {
-                print!("#");
-            } else {
-                print!(".");
-            }
+/// Draws the trench dug by the plan, shifted so that the top-left corner is at the origin.
+fn trench_frame(instr: &[((i64, i64), i64)]) -> Frame {
+    let mut trench: HashSet<(i64, i64)> = HashSet::from([(0, 0)]);
+    let mut loc = (0, 0);
+    for (dir, mag) in instr {
+        for _ in 0..*mag {
+            loc = (loc.0 + dir.0, loc.1 + dir.1);
+            trench.insert(loc);
         }
-        println!();
     }
+
+    let min_y = trench.iter().map(|(y, _)| *y).min().unwrap();
+    let min_x = trench.iter().map(|(_, x)| *x).min().unwrap();
+    let max_y = trench.iter().map(|(y, _)| *y).max().unwrap();
+    let max_x = trench.iter().map(|(_, x)| *x).max().unwrap();
+
+    let mut frame = Frame::new(
+        "Day 18: dug trench",
+        (max_x - min_x + 1) as usize,
+        (max_y - min_y + 1) as usize,
+    );
+    for (y, x) in trench {
+        let (row, col) = ((y - min_y) as usize, (x - min_x) as usize);
+        frame.set(row, col, '#', Some(Rgb::YELLOW));
+    }
+    frame.highlight((-min_y) as usize, (-min_x) as usize, Rgb::RED);
+    frame
 }
 
-fn compute_area(instr: &[((i64, i64), i64)]) -> Option<i64> {
+fn compute_area(instr: &[((i64, i64), i64)]) -> Option<Answer> {
     let mut path: Vec<(i64, i64)> = vec![(0, 0)];
 
     for (dir, mag) in instr {
@@ -26,19 +41,19 @@ fn compute_area(instr: &[((i64, i64), i64)]) -> Option<i64> {
         path.push((last.0 + dir.0 * mag, last.1 + dir.1 * mag));
     }
 
-    let perim: i64 = instr.iter().map(|(_, mag)| mag).sum();
+    let perim: Answer = instr.iter().map(|(_, mag)| *mag).sum();
 
     let area = path
         .windows(2)
-        .map(|arr| (arr[1].0 * arr[0].1 - arr[1].1 * arr[0].0))
-        .sum::<i64>()
+        .map(|arr| Answer::from(arr[1].0) * arr[0].1 - Answer::from(arr[1].1) * arr[0].0)
+        .sum::<Answer>()
         .abs()
         / 2;
 
     Some(area + perim / 2 + 1)
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
+pub fn part_one(input: &str) -> Option<Answer> {
     let instr: Vec<((i64, i64), i64)> = input
         .lines()
         .map(|line| {
@@ -54,10 +69,11 @@ pub fn part_one(input: &str) -> Option<i64> {
         })
         .collect();
 
+    viz::emit(|| trench_frame(&instr));
     compute_area(&instr)
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
+pub fn part_two(input: &str) -> Option<Answer> {
     let instr: Vec<((i64, i64), i64)> = input
         .lines()
         .map(|line| {
@@ -81,16 +97,123 @@ pub fn part_two(input: &str) -> Option<i64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::Differential;
+    use std::collections::{HashMap, VecDeque};
+
+    /// Cells of the trench in digging order, starting and ending at the origin.
+    fn trench(input: &str) -> Vec<(i64, i64)> {
+        let mut loc = (0, 0);
+        let mut cells = vec![loc];
+        for line in input.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let dir = match fields[0] {
+                "R" => (0, 1),
+                "L" => (0, -1),
+                "U" => (-1, 0),
+                _ => (1, 0),
+            };
+            for _ in 0..fields[1].parse::<i64>().unwrap() {
+                loc = (loc.0 + dir.0, loc.1 + dir.1);
+                cells.push(loc);
+            }
+        }
+        cells
+    }
+
+    /// Whether the plan digs a closed loop that doesn't touch itself, cells of the trench are only
+    /// next to the cells dug right before and after them.
+    fn is_simple_loop(input: &str) -> bool {
+        let cells = trench(input);
+        if cells.last() != Some(&(0, 0)) {
+            return false;
+        }
+        let cells = &cells[..cells.len() - 1];
+        let index: HashMap<(i64, i64), usize> = cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (*cell, i))
+            .collect();
+        index.len() == cells.len()
+            && cells.iter().enumerate().all(|(i, (y, x))| {
+                [(y - 1, *x), (y + 1, *x), (*y, x - 1), (*y, x + 1)]
+                    .iter()
+                    .filter_map(|next| index.get(next))
+                    .all(|j| (i + 1) % cells.len() == *j || (j + 1) % cells.len() == i)
+            })
+    }
+
+    /// Digs the trench, flood fills the outside and counts everything else.
+    fn flood_fill(input: &str) -> Option<Answer> {
+        let trench: HashSet<(i64, i64)> = trench(input).into_iter().collect();
+        let min_y = trench.iter().map(|(y, _)| *y).min()? - 1;
+        let min_x = trench.iter().map(|(_, x)| *x).min()? - 1;
+        let max_y = trench.iter().map(|(y, _)| *y).max()? + 1;
+        let max_x = trench.iter().map(|(_, x)| *x).max()? + 1;
+
+        let mut outside = HashSet::from([(min_y, min_x)]);
+        let mut queue = VecDeque::from([(min_y, min_x)]);
+        while let Some((y, x)) = queue.pop_front() {
+            for next in [(y - 1, x), (y + 1, x), (y, x - 1), (y, x + 1)] {
+                let in_bounds =
+                    (min_y..=max_y).contains(&next.0) && (min_x..=max_x).contains(&next.1);
+                if in_bounds && !trench.contains(&next) && outside.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        Some(Answer::from((max_y - min_y + 1) * (max_x - min_x + 1)) - outside.len())
+    }
+
+    /// Shortens a pair of opposite moves by one, which keeps the loop closed.
+    fn shorten_moves(input: &str) -> Vec<String> {
+        let lines: Vec<Vec<&str>> = input
+            .lines()
+            .map(|line| line.split_whitespace().collect())
+            .collect();
+        let opposite =
+            |a: &str, b: &str| matches!((a, b), ("R", "L") | ("L", "R") | ("U", "D") | ("D", "U"));
+
+        let mut smaller = Vec::new();
+        for i in 0..lines.len() {
+            for j in i + 1..lines.len() {
+                let (a, b) = (&lines[i], &lines[j]);
+                if !opposite(a[0], b[0]) || a[1] == "1" || b[1] == "1" {
+                    continue;
+                }
+                let shortened: Vec<String> = lines
+                    .iter()
+                    .enumerate()
+                    .map(|(k, line)| {
+                        let mag: i64 = line[1].parse().unwrap();
+                        let mag = if k == i || k == j { mag - 1 } else { mag };
+                        format!("{} {mag} {}", line[0], line[2])
+                    })
+                    .collect();
+                smaller.push(shortened.join("\n"));
+            }
+        }
+        smaller
+    }
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(62));
+        assert_eq!(result, Some(Answer::from(62)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(952408144115));
+        assert_eq!(result, Some(Answer::from(952408144115_i64)));
+    }
+
+    #[test]
+    fn test_part_one_against_oracle() {
+        Differential::new(DAY, ("part_one", part_one), ("flood_fill", flood_fill))
+            .sizes(1..=24)
+            .filter(is_simple_loop)
+            .shrink(shorten_moves)
+            .run();
     }
 }
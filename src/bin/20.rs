@@ -1,13 +1,18 @@
-use std::collections::{HashMap, VecDeque};
+use advent_of_code::debugger::Simulation;
+use advent_of_code::dot::{self, Graph};
+use advent_of_code::viz::{Frame, Rgb};
+use advent_of_code::Answer;
+use std::collections::{HashMap, HashSet, VecDeque};
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, step: simulation);
+
+const N_PUSHES: usize = 1000;
 
 #[derive(Debug, Clone)]
 struct Module {
     name: String,
     next: Vec<String>,
     mod_type: ModuleType,
-    high: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -28,21 +33,18 @@ impl Module {
             Module {
                 name: mod_type.to_string(),
                 next,
-                high: false,
                 mod_type: ModuleType::Broadcast,
             }
         } else if mod_type.find('%').is_some() {
             Module {
                 name: mod_type[1..].to_string(),
                 next,
-                high: false,
                 mod_type: ModuleType::FlipFlop { on: false },
             }
         } else {
             Module {
                 name: mod_type[1..].to_string(),
                 next,
-                high: false,
                 mod_type: ModuleType::Conjunction {
                     last: HashMap::new(),
                 },
@@ -66,112 +68,247 @@ impl Module {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut mods: Vec<Module> = input.lines().map(Module::from_str).collect();
-    let mut name_to_prev: HashMap<String, Vec<String>> = HashMap::new();
-    name_to_prev.insert("output".to_string(), Vec::new());
-    let name_to_mod: HashMap<String, Module> = mods
-        .iter()
-        .cloned()
-        .map(|m| {
-            name_to_prev.insert(m.name.clone(), Vec::new());
-            (m.name.clone(), m.clone())
-        })
-        .collect();
-
-    // initialize all last maps
-    for prev in &mods {
-        for n in &prev.next {
-            if name_to_prev.contains_key(n) {
-                name_to_prev.get_mut(n).unwrap().push(prev.name.clone());
-            } else {
-                name_to_prev.insert(n.to_string(), vec![prev.name.clone()]);
+/// Builds the module graph, shapes mark the module type and untyped modules are drawn as sinks.
+fn module_graph(mods: &[Module]) -> Graph {
+    let mut graph = Graph::digraph("day 20");
+    graph.node("button").attr("shape", "plaintext");
+    graph.edge("button", "broadcaster");
+
+    let names: HashSet<&str> = mods.iter().map(|m| m.name.as_str()).collect();
+    for m in mods {
+        let node = graph.node(&m.name);
+        match m.mod_type {
+            ModuleType::FlipFlop { .. } => node.attr("label", format!("%{}", m.name)),
+            ModuleType::Conjunction { .. } => node
+                .attr("label", format!("&{}", m.name))
+                .attr("shape", "box")
+                .attr("color", "blue"),
+            ModuleType::Broadcast => node.attr("shape", "doublecircle"),
+        };
+
+        for n in &m.next {
+            graph.edge(&m.name, n);
+            if !names.contains(n.as_str()) {
+                graph
+                    .node(n)
+                    .attr("shape", "doubleoctagon")
+                    .attr("color", "red");
             }
         }
     }
+    graph
+}
 
-    for m in mods.iter_mut() {
-        if let ModuleType::Conjunction { ref mut last } = m.mod_type {
-            *last = name_to_prev
-                .get(&m.name)
-                .unwrap()
-                .iter()
-                .map(|s| (s.clone(), false))
-                .collect::<HashMap<String, bool>>();
+/// Pulses waiting to be delivered as `(receiver, high, sender)`.
+type PulseQueue = VecDeque<(Module, bool, String)>;
+
+#[derive(Clone)]
+pub struct Machine {
+    name_to_mod: HashMap<String, Module>,
+    to_pulse: PulseQueue,
+    presses: usize,
+    count_low: u32,
+    count_high: u32,
+}
+
+impl Machine {
+    fn new(mut mods: Vec<Module>) -> Self {
+        let mut name_to_prev: HashMap<String, Vec<String>> = HashMap::new();
+        name_to_prev.insert("output".to_string(), Vec::new());
+        for m in &mods {
+            name_to_prev.insert(m.name.clone(), Vec::new());
         }
-    }
 
-    let mut name_to_mod: HashMap<String, Module> = mods
-        .iter()
-        .cloned()
-        .map(|m| (m.name.clone(), m.clone()))
-        .collect();
-    let mut count_low = 0;
-    let mut count_high = 0;
+        // initialize all last maps
+        for prev in &mods {
+            for n in &prev.next {
+                if name_to_prev.contains_key(n) {
+                    name_to_prev.get_mut(n).unwrap().push(prev.name.clone());
+                } else {
+                    name_to_prev.insert(n.to_string(), vec![prev.name.clone()]);
+                }
+            }
+        }
 
-    const N_PUSHES: usize = 1000;
+        for m in mods.iter_mut() {
+            if let ModuleType::Conjunction { ref mut last } = m.mod_type {
+                *last = name_to_prev
+                    .get(&m.name)
+                    .unwrap()
+                    .iter()
+                    .map(|s| (s.clone(), false))
+                    .collect::<HashMap<String, bool>>();
+            }
+        }
 
-    for _ in 0..N_PUSHES {
-        let mut to_pulse: VecDeque<(Module, bool, String)> = VecDeque::new();
-        to_pulse.push_back((
-            name_to_mod.get("broadcaster").unwrap().clone(),
+        Machine {
+            name_to_mod: mods.iter().map(|m| (m.name.clone(), m.clone())).collect(),
+            to_pulse: VecDeque::new(),
+            presses: 0,
+            count_low: 0,
+            count_high: 0,
+        }
+    }
+
+    fn press_button(&mut self) {
+        self.to_pulse.push_back((
+            self.name_to_mod.get("broadcaster").unwrap().clone(),
             false,
             "button".to_string(),
         ));
-        count_low += 1;
-        while let Some((mut m, pulse, prev)) = to_pulse.pop_front() {
-            let saved_name = m.name.clone();
-            if let ModuleType::Conjunction { ref mut last } = m.mod_type {
-                last.insert(prev.to_string(), pulse);
+        self.count_low += 1;
+        self.presses += 1;
+    }
+
+    /// Delivers the next queued pulse. Returns `false` once no pulses are left.
+    fn process_pulse(&mut self) -> bool {
+        let Some((mut m, pulse, prev)) = self.to_pulse.pop_front() else {
+            return false;
+        };
+
+        let saved_name = m.name.clone();
+        if let ModuleType::Conjunction { ref mut last } = m.mod_type {
+            last.insert(prev.to_string(), pulse);
+        }
+
+        // 1. send a pulse to module
+        if let Some(next_pulse) = m.pulse(pulse) {
+            // 2. send a pulse to neighbors
+            self.name_to_mod.insert(m.name.clone(), m.clone());
+            for v in &m.next {
+                if next_pulse {
+                    self.count_high += 1;
+                } else {
+                    self.count_low += 1;
+                }
+
+                if self.name_to_mod.contains_key(v) {
+                    self.to_pulse.push_back((
+                        self.name_to_mod.get(v).unwrap().clone(),
+                        next_pulse,
+                        saved_name.clone(),
+                    ));
+                }
             }
+        }
 
-            // dbg!(&m, pulse, &prev);
-            // 1. send a pulse to module
-            if let Some(next_pulse) = m.pulse(pulse) {
-                // 2. send a pulse to neighbors
-                // dbg!("propogating", next_pulse);
-                name_to_mod.insert(m.name.clone(), m.clone());
-                for v in &m.next {
-                    if next_pulse {
-                        // println!("high pulse to {}", { v });
-                        count_high += 1;
+        !self.to_pulse.is_empty()
+    }
+}
+
+impl Simulation for Machine {
+    /// Delivers one pulse, or presses the button if all pulses have been delivered.
+    fn step(&mut self) -> bool {
+        if self.to_pulse.is_empty() {
+            self.press_button();
+        } else {
+            self.process_pulse();
+        }
+        !(self.to_pulse.is_empty() && self.presses == N_PUSHES)
+    }
+
+    fn frame(&self) -> Frame {
+        let receiver = self.to_pulse.front().map(|(m, _, _)| m.name.as_str());
+        let mut names: Vec<&String> = self.name_to_mod.keys().collect();
+        names.sort();
+
+        let mut lines: Vec<(String, Rgb)> = Vec::new();
+        for name in names {
+            let marker = if receiver == Some(name.as_str()) {
+                '>'
+            } else {
+                ' '
+            };
+            let (line, color) = match &self.name_to_mod[name].mod_type {
+                ModuleType::Broadcast => (format!("{marker} {name}"), Rgb::WHITE),
+                ModuleType::FlipFlop { on: true } => (format!("{marker} %{name} on"), Rgb::GREEN),
+                ModuleType::FlipFlop { on: false } => (format!("{marker} %{name} off"), Rgb::GRAY),
+                ModuleType::Conjunction { last } => {
+                    let mut inputs: Vec<String> = last
+                        .iter()
+                        .map(|(k, v)| format!("{k}={}", u8::from(*v)))
+                        .collect();
+                    inputs.sort();
+                    // a conjunction that remembers only high pulses sends a low pulse.
+                    let color = if last.values().all(|v| *v) {
+                        Rgb::YELLOW
                     } else {
-                        // println!("low pulse to {}", { v });
-                        count_low += 1;
-                    }
-
-                    if name_to_mod.contains_key(v) {
-                        to_pulse.push_back((
-                            name_to_mod.get(v).unwrap().clone(),
-                            next_pulse,
-                            saved_name.clone(),
-                        ));
-                    }
+                        Rgb::BLUE
+                    };
+                    (format!("{marker} &{name} [{}]", inputs.join(" ")), color)
                 }
+            };
+            lines.push((line, color));
+        }
+
+        // pad lines with spaces, the frame fills missing cells with dots.
+        let width = lines.iter().map(|(line, _)| line.len()).max().unwrap_or(0);
+        let text: Vec<String> = lines
+            .iter()
+            .map(|(line, _)| format!("{line:width$}"))
+            .collect();
+        let title = format!("Day 20: modules after {} button presses", self.presses);
+        let mut frame = Frame::from_text(title, &text.join("\n"));
+        for (row, (line, color)) in lines.iter().enumerate() {
+            for col in 0..line.chars().count() {
+                frame.highlight(row, col, *color);
+            }
+            if line.starts_with('>') {
+                frame.highlight(row, 0, Rgb::RED);
             }
         }
+        frame
+    }
+
+    fn state(&self) -> Vec<(&'static str, String)> {
+        let mut state = vec![
+            ("button presses", self.presses.to_string()),
+            ("low pulses", self.count_low.to_string()),
+            ("high pulses", self.count_high.to_string()),
+            ("queued pulses", self.to_pulse.len().to_string()),
+        ];
+        if let Some((m, high, prev)) = self.to_pulse.front() {
+            let pulse = if *high { "high" } else { "low" };
+            state.push(("next pulse", format!("{prev} -{pulse}-> {}", m.name)));
+        }
+        state
+    }
+}
+
+pub fn simulation(input: &str) -> Machine {
+    Machine::new(input.lines().map(Module::from_str).collect())
+}
+
+pub fn part_one(input: &str) -> Option<Answer> {
+    let mods: Vec<Module> = input.lines().map(Module::from_str).collect();
+    dot::export(|| module_graph(&mods));
+
+    let mut machine = Machine::new(mods);
+    for _ in 0..N_PUSHES {
+        machine.press_button();
+        while machine.process_pulse() {}
     }
-    dbg!(count_low, count_high);
 
-    Some(count_low * count_high)
+    Some(Answer::from(machine.count_low) * machine.count_high)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(_input: &str) -> Option<Answer> {
     None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&fs::read_to_string("./data/examples/20_2.txt").unwrap());
-        assert_eq!(result, Some(32000000));
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ));
+        assert_eq!(result, Some(Answer::from(32000000)));
 
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11687500));
+        assert_eq!(result, Some(Answer::from(11687500)));
     }
 
     #[test]
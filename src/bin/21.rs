@@ -1,8 +1,79 @@
+use advent_of_code::validate::{self, Validation, Validator};
+use advent_of_code::{poly, Answer};
 use std::collections::{HashMap, VecDeque};
 
-advent_of_code::solution!(21);
+advent_of_code::solution!(21, validate: validate);
 
-pub fn part_one(input: &str) -> Option<u32> {
+/// Steps the elf takes in part two.
+const STEPS: usize = 26501365;
+
+pub fn validate(input: &str) -> Validation {
+    let mut validator = Validator::new();
+    if let Some(size) = check_garden(&mut validator, input) {
+        validator.require(check_steps(size, STEPS));
+    }
+    validator.finish()
+}
+
+/// Checks the garden the part two formula relies on and returns its size.
+fn check_garden(validator: &mut Validator, input: &str) -> Option<usize> {
+    validator.require(validate::grid_chars(input, ".#S"));
+    let dimensions = validator.require(validate::rectangular_grid(input));
+    let start = validator.require(validate::find_unique(input, 'S'));
+    let ((rows, cols), (row, col)) = dimensions.zip(start)?;
+
+    // the part two formula expands the garden in whole tiles reached along straight lines from `S`.
+    validator
+        .check(
+            rows == cols,
+            format!("garden must be square, got {rows}x{cols}"),
+        )
+        .check(rows % 2 == 1, "garden must have an odd size")
+        .check(
+            row == rows / 2 && col == cols / 2,
+            format!("'S' must be centered, found it at row {row}, column {col}"),
+        );
+
+    let lines: Vec<&str> = input.lines().collect();
+    validator
+        .check(
+            !lines[row].contains('#'),
+            "the row of 'S' must be free of rocks",
+        )
+        .check(
+            lines.iter().all(|line| line.chars().nth(col) != Some('#')),
+            "the column of 'S' must be free of rocks",
+        );
+
+    (rows == cols).then_some(rows)
+}
+
+/// Checks that `steps` is odd and reaches the edge of a tile an even number of whole tiles past
+/// the center one of a garden of `size`, e.g. `26501365 = 202300 * 131 + 65`.
+fn check_steps(size: usize, steps: usize) -> Result<(), String> {
+    let Some(past_center) = steps.checked_sub(size / 2) else {
+        return Err(format!(
+            "{steps} steps must reach the edge of the center tile of size {size}"
+        ));
+    };
+    let (tiles, rest) = (past_center / size, past_center % size);
+    if rest != 0 {
+        return Err(format!(
+            "{steps} steps must end at the edge of a tile of size {size}, they end {rest} plots past it"
+        ));
+    }
+    if !tiles.is_multiple_of(2) {
+        return Err(format!(
+            "{steps} steps must cross an even number of tiles, they cross {tiles}"
+        ));
+    }
+    if steps.is_multiple_of(2) {
+        return Err(format!("{steps} steps must be odd"));
+    }
+    Ok(())
+}
+
+pub fn part_one(input: &str) -> Option<Answer> {
     let grid_map: HashMap<(isize, isize), char> = input
         .lines()
         .enumerate()
@@ -27,7 +98,8 @@ pub fn part_one(input: &str) -> Option<u32> {
         visited
             .values()
             .filter(|v| **v % 2 == 0 && **v <= 64)
-            .count() as u32,
+            .count()
+            .into(),
     )
 }
 
@@ -50,64 +122,198 @@ fn explore_dec(
     visited
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut grid_map: HashMap<(isize, isize), char> = input
-        .lines()
-        .enumerate()
-        .flat_map(|(x, row)| {
-            row.chars()
-                .enumerate()
-                .map(|(y, c)| ((x as isize, y as isize), c))
-                .collect::<Vec<_>>()
-        })
-        .collect();
+/// Counts the plots reachable in exactly `steps` steps on the infinitely repeated garden, once for
+/// every number of steps in `steps`.
+fn count_reachable(input: &str, steps: &[usize]) -> Vec<usize> {
+    let grid: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    let (rows, cols) = (grid.len() as isize, grid[0].len() as isize);
+    let Some((start_row, start_col)) = validate::find_unique(input, 'S').ok() else {
+        return vec![0; steps.len()];
+    };
 
-    let mut start_loc = (0, 0);
+    // nothing further than the largest number of steps away can be reached.
+    let radius = steps.iter().copied().max().unwrap_or(0) as isize;
+    let width = 2 * radius + 1;
+    let index = |(row, col): (isize, isize)| (row * width + col) as usize;
+    let is_plot = |(row, col): (isize, isize)| {
+        let (row, col) = (
+            row - radius + start_row as isize,
+            col - radius + start_col as isize,
+        );
+        grid[row.rem_euclid(rows) as usize][col.rem_euclid(cols) as usize] != b'#'
+    };
 
-    for (key, val) in &grid_map {
-        if val == &'S' {
-            start_loc = *key;
+    let mut distances = vec![usize::MAX; (width * width) as usize];
+    distances[index((radius, radius))] = 0;
+    let mut queue = VecDeque::from([(radius, radius)]);
+    while let Some((row, col)) = queue.pop_front() {
+        let dist = distances[index((row, col))];
+        for next in [
+            (row - 1, col),
+            (row + 1, col),
+            (row, col - 1),
+            (row, col + 1),
+        ] {
+            let inside = (0..width).contains(&next.0) && (0..width).contains(&next.1);
+            if inside && is_plot(next) && distances[index(next)] == usize::MAX {
+                distances[index(next)] = dist + 1;
+                queue.push_back(next);
+            }
         }
     }
-    grid_map.insert(start_loc, '.');
-
-    let grid_max = grid_map.keys().map(|k| k.1).max()? + 1;
-
-    let visited = explore_dec(start_loc, &grid_map);
-    let even_corners = visited
-        .values()
-        .filter(|v| **v % 2 == 0 && **v > 65)
-        .count();
 
-    let odd_corners = visited
-        .values()
-        .filter(|v| **v % 2 == 1 && **v > 65)
-        .count();
+    // an elf can step back and forth, so every plot of the same parity within reach counts.
+    steps
+        .iter()
+        .map(|steps| {
+            distances
+                .iter()
+                .filter(|dist| **dist <= *steps && **dist % 2 == steps % 2)
+                .count()
+        })
+        .collect()
+}
 
-    let even_full = visited.values().filter(|v| **v % 2 == 0).count();
-    let odd_full = visited.values().filter(|v| **v % 2 == 1).count();
+/// Counts the plots reachable in exactly `steps` steps on the infinitely repeated garden.
+/// Fails if `steps` don't fit the tiles of the garden as checked by [`check_steps`], or if the
+/// count does not grow quadratically in the number of tiles.
+fn reachable_plots(input: &str, steps: usize) -> Result<Answer, String> {
+    let size = input.lines().count();
+    check_steps(size, steps)?;
+    let half = size / 2;
 
-    let n = ((26501365 - (grid_max / 2)) / grid_max) as usize;
+    // the diamond of reachable plots grows by whole tiles, so the count is quadratic in the number
+    // of tiles walked. It alternates with the parity of tiles, so sample an even number only.
+    let samples: Vec<usize> = [0, 2, 4].iter().map(|tiles| half + tiles * size).collect();
+    let points: Vec<(i128, i128)> = samples
+        .iter()
+        .zip(count_reachable(input, &samples))
+        .map(|(steps, count)| (*steps as i128, count as i128))
+        .collect();
 
-    let p2 = ((n + 1) * (n + 1)) * odd_full + (n * n) * even_full - (n + 1) * odd_corners
-        + n * even_corners;
+    let count = poly::lagrange(&points, steps as i128);
+    count.to_integer().map(Answer::from).ok_or_else(|| {
+        format!("the reachable plots do not grow quadratically with the tiles, got {count}")
+    })
+}
 
-    Some(p2 as u64)
+pub fn part_two(input: &str) -> Option<Result<Answer, String>> {
+    Some(reachable_plots(input, STEPS))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::differential::{self, Differential};
+    use std::collections::HashSet;
+
+    /// Moves every elf to all neighboring plots, `steps` times. Repeats the garden if `infinite`.
+    fn simulate_steps(input: &str, steps: usize, infinite: bool) -> usize {
+        let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let (rows, cols) = (grid.len() as isize, grid[0].len() as isize);
+        let is_plot = |(row, col): (isize, isize)| -> bool {
+            let inside = (0..rows).contains(&row) && (0..cols).contains(&col);
+            if !infinite && !inside {
+                return false;
+            }
+            grid[row.rem_euclid(rows) as usize][col.rem_euclid(cols) as usize] != '#'
+        };
+
+        let start = validate::find_unique(input, 'S').unwrap();
+        let mut positions = HashSet::from([(start.0 as isize, start.1 as isize)]);
+        for _ in 0..steps {
+            positions = positions
+                .iter()
+                .flat_map(|(row, col)| {
+                    [
+                        (row - 1, *col),
+                        (row + 1, *col),
+                        (*row, col - 1),
+                        (*row, col + 1),
+                    ]
+                })
+                .filter(|next| is_plot(*next))
+                .collect();
+        }
+        positions.len()
+    }
+
+    /// Steps that reach the edge of the garden `tiles` tiles past the center one.
+    fn tile_steps(input: &str, tiles: usize) -> usize {
+        let size = input.lines().count();
+        tiles * size + size / 2
+    }
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(42));
+        assert_eq!(result, Some(Answer::from(42)));
+    }
+
+    /// Whether the garden fits the part two formula, regardless of the number of steps.
+    fn is_valid_garden(input: &str) -> bool {
+        let mut validator = Validator::new();
+        check_garden(&mut validator, input);
+        validator.finish().is_ok()
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let error =
+            "26501365 steps must end at the edge of a tile of size 11, they end 6 plots past it";
+        assert_eq!(part_two(&input), Some(Err(error.into())));
+        assert!(validate(&input).unwrap_err().contains(&error.to_string()));
+    }
+
+    #[test]
+    fn checks_steps() {
+        assert_eq!(check_steps(131, STEPS), Ok(()));
+        assert_eq!(check_steps(11, 5 + 4 * 11), Ok(()));
+        assert!(check_steps(11, 4).is_err());
+        assert!(check_steps(11, 5 + 3 * 11).is_err());
+        assert!(check_steps(9, 4 + 2 * 9).is_err());
+    }
+
+    #[test]
+    fn test_part_one_against_oracle() {
+        Differential::new(
+            DAY,
+            ("part_one", part_one),
+            ("simulate_steps", |input: &str| {
+                Some(Answer::from(simulate_steps(input, 64, false)))
+            }),
+        )
+        .sizes(1..=21)
+        .seeds(3)
+        .shrink(|input| differential::blank_cells(input, '.'))
+        .filter(is_valid_garden)
+        .run();
+    }
+
+    #[test]
+    fn test_repeated_garden_against_oracle() {
+        // 0, 2 and 4 tiles are sampled, 6 is extrapolated.
+        for tiles in [2, 6] {
+            Differential::new(
+                DAY,
+                ("reachable_plots", move |input: &str| {
+                    reachable_plots(input, tile_steps(input, tiles)).ok()
+                }),
+                ("simulate_steps", move |input: &str| {
+                    Some(Answer::from(simulate_steps(
+                        input,
+                        tile_steps(input, tiles),
+                        true,
+                    )))
+                }),
+            )
+            .sizes(3..=15)
+            .seeds(3)
+            .shrink(|input| differential::blank_cells(input, '.'))
+            // the number of steps must be odd like in the real input.
+            .filter(|input| is_valid_garden(input) && tile_steps(input, 0) % 2 == 1)
+            .run();
+        }
     }
 }
@@ -1,171 +1,130 @@
-use std::collections::{HashMap, HashSet};
-
-advent_of_code::solution!(22);
-
-#[derive(Debug, Clone, Eq, PartialEq)]
-struct Brick {
-    start: Point,
-    end: Point,
-    id: usize,
+use advent_of_code::bricks::{Brick, Stack};
+use advent_of_code::debugger::Simulation;
+use advent_of_code::viz::{Frame, Rgb};
+use advent_of_code::Answer;
+use std::collections::{HashMap, VecDeque};
+
+advent_of_code::solution!(22, step: simulation);
+
+/// Lets bricks fall one at a time, lowest first.
+#[derive(Debug, Clone)]
+pub struct Settling {
+    pending: VecDeque<Brick>,
+    stack: Stack,
+    last: Option<usize>,
 }
 
-impl Brick {
-    fn points(&self) -> Vec<Point> {
-        let mut points: Vec<Point> = Vec::new();
-        for x in std::cmp::min(self.start.x, self.end.x)..=std::cmp::max(self.start.x, self.end.x) {
-            for y in
-                std::cmp::min(self.start.y, self.end.y)..=std::cmp::max(self.start.y, self.end.y)
-            {
-                for z in std::cmp::min(self.start.z, self.end.z)
-                    ..=std::cmp::max(self.start.z, self.end.z)
-                {
-                    points.push(Point { x, y, z });
-                }
-            }
+impl Settling {
+    /// Expects the bricks to be sorted by their lowest z coordinate.
+    fn new(bricks: Vec<Brick>) -> Self {
+        Settling {
+            pending: bricks.into(),
+            stack: Stack::new(),
+            last: None,
         }
-        points
     }
 }
-#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
-struct Point {
-    x: isize,
-    y: isize,
-    z: isize,
-}
 
-impl Point {
-    fn from_str(s: &str) -> Self {
-        let coords = s.split(',').collect::<Vec<_>>();
-        let x = coords[0].parse::<isize>().unwrap();
-        let y = coords[1].parse::<isize>().unwrap();
-        let z = coords[2].parse::<isize>().unwrap();
-        Point { x, y, z }
+impl Simulation for Settling {
+    fn step(&mut self) -> bool {
+        if let Some(brick) = self.pending.pop_front() {
+            self.last = Some(self.stack.drop_brick(brick));
+        }
+        !self.pending.is_empty()
     }
-}
 
-fn fall_bricks(bricks: Vec<Brick>) -> Option<(u32, u32)> {
-    let mut brick_supporters: HashMap<usize, HashSet<usize>> = HashMap::new();
-    let mut brick_supported: HashMap<usize, HashSet<usize>> = HashMap::new();
-    let mut z_map: HashMap<(isize, isize), isize> = HashMap::new();
-    let mut grid: HashMap<Point, usize> = HashMap::new();
-    let len_bricks = bricks.len();
-
-    for b in bricks {
-        brick_supporters.insert(b.id, HashSet::new());
-        brick_supported.insert(b.id, HashSet::new());
-        let points = b.points();
-
-        // find z at highest collision
-        let new_z = points
-            .iter()
-            .map(|p| *z_map.entry((p.x, p.y)).or_insert(0))
-            .max()?
-            + 1;
-
-        let b_bottom = std::cmp::min(b.start.z, b.end.z);
-
-        // loop through all points for this brick
-        for p in points {
-            let possible_collision_point = Point {
-                x: p.x,
-                y: p.y,
-                z: new_z - 1,
-            };
-            // if there is a brick at this grid, update the supporters/supportin
-            if let Some(existing_brick_id) = grid.get(&possible_collision_point) {
-                brick_supporters
-                    .entry(*existing_brick_id)
-                    .or_insert(HashSet::new())
-                    .insert(b.id);
-                brick_supported
-                    .entry(b.id)
-                    .or_insert(HashSet::new())
-                    .insert(*existing_brick_id);
-            }
-
-            // update this position to be this grid
-            z_map.insert((p.x, p.y), new_z + (p.z - b_bottom));
-            grid.insert(
-                Point {
-                    x: p.x,
-                    y: p.y,
-                    z: new_z + (p.z - b_bottom),
-                },
-                b.id,
-            );
+    /// Shows the bricks from the front like the puzzle description, with x to the right and z
+    /// upwards. Settled bricks are white, falling bricks gray and the last settled brick red.
+    fn frame(&self) -> Frame {
+        // pending bricks are numbered like they will be once settled.
+        let all_points = || {
+            self.stack
+                .bricks()
+                .iter()
+                .chain(&self.pending)
+                .enumerate()
+                .flat_map(|(id, b)| b.cubes().map(move |p| (p, id)))
+        };
+        let max_x = all_points().map(|(p, _)| p.x).max().unwrap_or(0);
+        let max_z = all_points().map(|(p, _)| p.z).max().unwrap_or(0);
+
+        let title = format!("Day 22: {} bricks settled", self.stack.len());
+        let mut frame = Frame::new(title, max_x as usize + 1, max_z as usize + 1);
+        for col in 0..=max_x as usize {
+            frame.set(max_z as usize, col, '-', Some(Rgb::GRAY));
         }
-    }
 
-    let mut count = 0;
-    for (_b, supported) in brick_supporters.clone() {
-        // for each supporting brick B1, check for all the bricks Bn its supporting that # supporters(Bn) != 1
-        if supported.iter().all(|s| brick_supported[s].len() != 1) {
-            count += 1;
+        // bricks behind each other in y are drawn as '?'.
+        let mut cells: HashMap<(isize, isize), Option<usize>> = HashMap::new();
+        for (p, id) in all_points() {
+            cells
+                .entry((p.z, p.x))
+                .and_modify(|cell| {
+                    if *cell != Some(id) {
+                        *cell = None;
+                    }
+                })
+                .or_insert(Some(id));
         }
-    }
 
-    // PT 2 IDEA:
-    // for each brick, find the bricks that it is the sole supporter of
-    // add these bricks to the delete file
-    let mut acc_fall = 0;
-    for id in 0..len_bricks {
-        let mut explore = Vec::from([id]);
-        let mut will_fall: HashSet<usize> = HashSet::from([id]);
-        while let Some(new_id) = explore.pop() {
-            let to_remove = will_fall.clone();
-            for s in &brick_supporters[&new_id] {
-                if brick_supported[s]
-                    .clone()
-                    .iter()
-                    .filter(|x| !to_remove.contains(x))
-                    .count()
-                    == 0
-                    && !will_fall.contains(s)
-                {
-                    explore.push(*s);
-                    will_fall.insert(*s);
+        for ((z, x), cell) in cells {
+            let (ch, color) = match cell {
+                None => ('?', Rgb::YELLOW),
+                Some(id) => {
+                    let ch = char::from(b'A' + (id % 26) as u8);
+                    let color = if Some(id) == self.last {
+                        Rgb::RED
+                    } else if id < self.stack.len() {
+                        Rgb::WHITE
+                    } else {
+                        Rgb::GRAY
+                    };
+                    (ch, color)
                 }
-            }
+            };
+            frame.set((max_z - z) as usize, x as usize, ch, Some(color));
+        }
+        frame
+    }
+
+    fn state(&self) -> Vec<(&'static str, String)> {
+        let mut state = vec![("falling bricks", self.pending.len().to_string())];
+        if let Some(id) = self.last {
+            state.push(("last settled brick", id.to_string()));
+            state.push(("supported by", format!("{:?}", self.stack.supported_by(id))));
         }
-        acc_fall += will_fall.len() - 1;
+        state
     }
+}
+
+fn parse_bricks(input: &str) -> Vec<Brick> {
+    let mut bricks: Vec<Brick> = input.lines().map(|line| line.parse().unwrap()).collect();
+    bricks.sort_by_key(Brick::bottom);
+    bricks
+}
 
-    Some((count, acc_fall as u32))
+pub fn simulation(input: &str) -> Settling {
+    Settling::new(parse_bricks(input))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut bricks: Vec<_> = input
-        .lines()
-        .enumerate()
-        .map(|(i, line)| {
-            let (start, end) = line.split_once('~').unwrap();
-            let start = Point::from_str(start);
-            let end = Point::from_str(end);
-            let id = i;
-            Brick { start, end, id }
-        })
-        .collect();
-
-    bricks.sort_by_key(|brick| std::cmp::min(brick.start.z, brick.end.z));
-
-    Some(fall_bricks(bricks).unwrap().0)
+pub fn part_one(input: &str) -> Option<Answer> {
+    let stack = Stack::settle(parse_bricks(input));
+    Some(
+        (0..stack.len())
+            .filter(|id| stack.is_removable(*id))
+            .count()
+            .into(),
+    )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut bricks: Vec<_> = input
-        .lines()
-        .enumerate()
-        .map(|(i, line)| {
-            let (start, end) = line.split_once('~').unwrap();
-            let start = Point::from_str(start);
-            let end = Point::from_str(end);
-            let id = i;
-            Brick { start, end, id }
-        })
-        .collect();
-
-    bricks.sort_by_key(|brick| std::cmp::min(brick.start.z, brick.end.z));
-    Some(fall_bricks(bricks).unwrap().1)
+pub fn part_two(input: &str) -> Option<Answer> {
+    let stack = Stack::settle(parse_bricks(input));
+    let dominators = stack.dominator_tree();
+    Some(
+        (0..stack.len())
+            .map(|id| dominators.chain_reaction(id))
+            .sum(),
+    )
 }
 
 #[cfg(test)]
@@ -175,12 +134,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5));
+        assert_eq!(result, Some(Answer::from(5)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7));
+        assert_eq!(result, Some(Answer::from(7)));
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::Answer;
 advent_of_code::solution!(24);
 
 #[derive(Debug, Clone, Copy)]
@@ -64,7 +65,7 @@ fn count_intersections(hailstones: &[Hail], min: f64, max: f64) -> u32 {
         .count() as u32
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<Answer> {
     let hailstones: Vec<_> = input
         .lines()
         .map(|line| {
@@ -77,14 +78,14 @@ pub fn part_one(input: &str) -> Option<u32> {
         .collect();
 
     // Some(count_intersections(&hailstones, 7.0, 27.0))
-    Some(count_intersections(
+    Some(Answer::from(count_intersections(
         &hailstones,
         200000000000000.0,
         400000000000000.0,
-    ))
+    )))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(_input: &str) -> Option<Answer> {
     None
 }
 
@@ -95,12 +96,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(0));
+        assert_eq!(result, Some(Answer::from(0)));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(47));
+        assert_eq!(result, Some(Answer::from(47)));
     }
 }
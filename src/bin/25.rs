@@ -1,12 +1,42 @@
+use advent_of_code::dot::{self, Graph};
+use advent_of_code::Answer;
 use petgraph::algo::{astar, kosaraju_scc};
 use petgraph::graph::{NodeIndex, UnGraph};
+use petgraph::visit::EdgeRef;
 use rand::prelude::*;
 
 use std::collections::HashMap;
 
 advent_of_code::solution!(25);
 
-pub fn part_one(input: &str) -> Option<u32> {
+/// Builds the component graph with nodes colored by group and the cut wires highlighted.
+fn component_graph(
+    g: &UnGraph<&str, ()>,
+    groups: &[Vec<NodeIndex>],
+    cut: &[(NodeIndex, NodeIndex)],
+) -> Graph {
+    const COLORS: [&str; 4] = ["blue", "darkgreen", "orange", "purple"];
+
+    let mut graph = Graph::undirected("day 25");
+    for (i, group) in groups.iter().enumerate() {
+        for node in group {
+            graph.node(g[*node]).attr("color", COLORS[i % COLORS.len()]);
+        }
+    }
+    for edge in g.edge_references() {
+        graph.edge(g[edge.source()], g[edge.target()]);
+    }
+    for (a, b) in cut {
+        graph
+            .edge(g[*a], g[*b])
+            .attr("color", "red")
+            .attr("penwidth", 3)
+            .attr("style", "dashed");
+    }
+    graph
+}
+
+pub fn part_one(input: &str) -> Option<Answer> {
     let mut iids: HashMap<String, u32> = HashMap::new();
     let mut n = 0;
     let mut g = UnGraph::<_, ()>::new_undirected();
@@ -21,8 +51,8 @@ pub fn part_one(input: &str) -> Option<u32> {
             }
             let mut temp: Vec<_> = Vec::new();
             for s in right.trim().split(' ') {
-                if !iids.contains_key(&s.to_string()) {
-                    iids.insert(s.to_string(), n);
+                if let std::collections::hash_map::Entry::Vacant(e) = iids.entry(s.to_string()) {
+                    e.insert(n);
                     g.add_node(s);
                     n += 1;
                 }
@@ -58,6 +88,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     let mut pairs: Vec<_> = node_count.iter().collect();
     pairs.sort_by(|&(_, a), &(_, b)| b.cmp(a));
 
+    let mut cut = Vec::new();
     for i in 0..6 {
         for j in i..6 {
             if let Some(edge) = g.find_edge(*pairs[i].0, *pairs[j].0) {
@@ -67,13 +98,17 @@ pub fn part_one(input: &str) -> Option<u32> {
                 //     g.node_weight(*pairs[j].0).unwrap()
                 // );
                 g.remove_edge(edge);
+                cut.push((*pairs[i].0, *pairs[j].0));
             }
         }
     }
-    Some(kosaraju_scc(&g).iter().map(|v| v.len() as u32).product())
+
+    let groups = kosaraju_scc(&g);
+    dot::export(|| component_graph(&g, &groups, &cut));
+    Some(groups.iter().map(|v| v.len() as u32).product())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(_input: &str) -> Option<Answer> {
     None
 }
 
@@ -84,7 +119,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(54));
+        assert_eq!(result, Some(Answer::from(54)));
     }
 
     #[test]
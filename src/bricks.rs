@@ -0,0 +1,401 @@
+//! Physics of sand bricks falling onto a stack (day 22), with queries on which bricks hold up
+//! which other bricks.
+//!
+//! Bricks are dropped lowest first and numbered in that order. Every brick rests on the ground or
+//! on the bricks directly beneath it, so the "supported by" relation is a DAG rooted at the ground
+//! whose topological order is the drop order.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+/// A brick spanning all cubes between two corners, inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Brick {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Brick {
+    #[must_use]
+    pub fn bottom(&self) -> isize {
+        self.start.z.min(self.end.z)
+    }
+
+    #[must_use]
+    pub fn height(&self) -> isize {
+        (self.start.z - self.end.z).abs() + 1
+    }
+
+    /// The `(x, y)` columns covered by the brick.
+    pub fn footprint(&self) -> impl Iterator<Item = (isize, isize)> {
+        let (x0, x1) = (self.start.x.min(self.end.x), self.start.x.max(self.end.x));
+        let (y0, y1) = (self.start.y.min(self.end.y), self.start.y.max(self.end.y));
+        (x0..=x1).flat_map(move |x| (y0..=y1).map(move |y| (x, y)))
+    }
+
+    pub fn cubes(&self) -> impl Iterator<Item = Point> {
+        let bottom = self.bottom();
+        let top = bottom + self.height() - 1;
+        self.footprint()
+            .flat_map(move |(x, y)| (bottom..=top).map(move |z| Point { x, y, z }))
+    }
+
+    /// The same brick moved vertically so that its bottom is at `z`.
+    #[must_use]
+    pub fn lowered_to(self, z: isize) -> Brick {
+        let dz = z - self.bottom();
+        let shift = |p: Point| Point { z: p.z + dz, ..p };
+        Brick {
+            start: shift(self.start),
+            end: shift(self.end),
+        }
+    }
+}
+
+/// Parses a brick from its corners, e.g. `1,0,1~1,2,1`.
+impl FromStr for Brick {
+    type Err = BrickFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_point = |s: &str| -> Result<Point, BrickFromStrError> {
+            let coords = s
+                .split(',')
+                .map(|c| c.trim().parse::<isize>().map_err(|_| BrickFromStrError))
+                .collect::<Result<Vec<_>, _>>()?;
+            match coords[..] {
+                [x, y, z] => Ok(Point { x, y, z }),
+                _ => Err(BrickFromStrError),
+            }
+        };
+        let (start, end) = s.split_once('~').ok_or(BrickFromStrError)?;
+        Ok(Brick {
+            start: parse_point(start)?,
+            end: parse_point(end)?,
+        })
+    }
+}
+
+/// An error which can be returned when parsing a [`Brick`].
+#[derive(Debug)]
+pub struct BrickFromStrError;
+
+impl Error for BrickFromStrError {}
+
+impl Display for BrickFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a brick like `1,0,1~1,2,1`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Settled bricks, identified by the order they were dropped in.
+#[derive(Debug, Clone, Default)]
+pub struct Stack {
+    bricks: Vec<Brick>,
+    supports: Vec<Vec<usize>>,
+    supported_by: Vec<Vec<usize>>,
+    /// height and brick at the top of every column.
+    tops: HashMap<(isize, isize), (isize, usize)>,
+}
+
+impl Stack {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops all bricks, lowest first.
+    #[must_use]
+    pub fn settle(bricks: impl IntoIterator<Item = Brick>) -> Self {
+        let mut bricks: Vec<Brick> = bricks.into_iter().collect();
+        bricks.sort_by_key(Brick::bottom);
+
+        let mut stack = Stack::new();
+        for brick in bricks {
+            stack.drop_brick(brick);
+        }
+        stack
+    }
+
+    /// Lets `brick` fall until it rests on the ground or on other bricks and returns its id.
+    /// Bricks must be dropped in the order of their bottom, like [`Stack::settle`] does.
+    pub fn drop_brick(&mut self, brick: Brick) -> usize {
+        let id = self.bricks.len();
+        let below: Vec<(isize, usize)> = brick
+            .footprint()
+            .filter_map(|column| self.tops.get(&column).copied())
+            .collect();
+        let rest = below.iter().map(|(z, _)| *z).max().unwrap_or(0);
+
+        let mut supported_by: Vec<usize> = below
+            .iter()
+            .filter(|(z, _)| *z == rest)
+            .map(|(_, below)| *below)
+            .collect();
+        supported_by.sort_unstable();
+        supported_by.dedup();
+        for below in &supported_by {
+            self.supports[*below].push(id);
+        }
+
+        for column in brick.footprint() {
+            self.tops.insert(column, (rest + brick.height(), id));
+        }
+        self.bricks.push(brick.lowered_to(rest + 1));
+        self.supports.push(Vec::new());
+        self.supported_by.push(supported_by);
+        id
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bricks.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bricks.is_empty()
+    }
+
+    /// Settled bricks by id.
+    #[must_use]
+    pub fn bricks(&self) -> &[Brick] {
+        &self.bricks
+    }
+
+    /// Bricks resting directly on brick `id`, in ascending order.
+    #[must_use]
+    pub fn supports(&self, id: usize) -> &[usize] {
+        &self.supports[id]
+    }
+
+    /// Bricks directly beneath brick `id`, in ascending order. Empty if it rests on the ground.
+    #[must_use]
+    pub fn supported_by(&self, id: usize) -> &[usize] {
+        &self.supported_by[id]
+    }
+
+    /// Whether brick `id` can be disintegrated without any other brick falling.
+    #[must_use]
+    pub fn is_removable(&self, id: usize) -> bool {
+        self.supports[id]
+            .iter()
+            .all(|above| self.supported_by[*above].len() > 1)
+    }
+
+    /// Bricks that fall if all bricks in `removed` are disintegrated, in ascending order.
+    #[must_use]
+    pub fn falling(&self, removed: &[usize]) -> Vec<usize> {
+        let Some(first) = removed.iter().min() else {
+            return Vec::new();
+        };
+        let mut gone = vec![false; self.len()];
+        for id in removed {
+            gone[*id] = true;
+        }
+
+        // supporters are always dropped before the bricks they support.
+        let mut falling = Vec::new();
+        for id in first + 1..self.len() {
+            let supported_by = &self.supported_by[id];
+            if !gone[id] && !supported_by.is_empty() && supported_by.iter().all(|s| gone[*s]) {
+                gone[id] = true;
+                falling.push(id);
+            }
+        }
+        falling
+    }
+
+    /// Builds the dominator tree of the support DAG in `O((n + m) log n)`.
+    #[must_use]
+    pub fn dominator_tree(&self) -> DominatorTree {
+        DominatorTree::new(self)
+    }
+}
+
+/// Dominator tree of the support DAG, rooted at the ground.
+///
+/// Brick `a` dominates brick `b` if every way down from `b` to the ground passes through `a`, so
+/// removing `a` makes exactly the bricks it dominates fall.
+#[derive(Debug, Clone)]
+pub struct DominatorTree {
+    idom: Vec<Option<usize>>,
+    sizes: Vec<usize>,
+}
+
+impl DominatorTree {
+    fn new(stack: &Stack) -> Self {
+        let n = stack.len();
+        let ground = n;
+        let levels = (usize::BITS - n.leading_zeros()).max(1) as usize;
+        // ancestors[k][v] is the 2^k-th ancestor of v, the ground is its own ancestor.
+        let mut ancestors = vec![vec![ground; n + 1]; levels];
+        let mut depths = vec![0; n + 1];
+
+        // in a DAG visited in topological order, the immediate dominator of a brick is the
+        // nearest common dominator of the bricks it rests on.
+        let mut idom = Vec::with_capacity(n);
+        for id in 0..n {
+            let parent = stack
+                .supported_by(id)
+                .iter()
+                .copied()
+                .reduce(|a, b| lca(&ancestors, &depths, a, b))
+                .filter(|parent| *parent != ground);
+            idom.push(parent);
+
+            let parent = parent.unwrap_or(ground);
+            depths[id] = depths[parent] + 1;
+            ancestors[0][id] = parent;
+            for k in 1..levels {
+                ancestors[k][id] = ancestors[k - 1][ancestors[k - 1][id]];
+            }
+        }
+
+        let mut sizes = vec![1; n];
+        for id in (0..n).rev() {
+            if let Some(parent) = idom[id] {
+                sizes[parent] += sizes[id];
+            }
+        }
+
+        DominatorTree { idom, sizes }
+    }
+
+    /// The nearest brick whose removal makes brick `id` fall, or `None` if only the ground does.
+    #[must_use]
+    pub fn idom(&self, id: usize) -> Option<usize> {
+        self.idom[id]
+    }
+
+    /// Number of other bricks that fall if brick `id` is disintegrated.
+    #[must_use]
+    pub fn chain_reaction(&self, id: usize) -> usize {
+        self.sizes[id] - 1
+    }
+}
+
+/// Lowest common ancestor by binary lifting.
+fn lca(ancestors: &[Vec<usize>], depths: &[usize], mut a: usize, mut b: usize) -> usize {
+    if depths[a] < depths[b] {
+        (a, b) = (b, a);
+    }
+    for level in ancestors.iter().rev() {
+        if depths[level[a]] >= depths[b] {
+            a = level[a];
+        }
+    }
+    if a == b {
+        return a;
+    }
+    for level in ancestors.iter().rev() {
+        if level[a] != level[b] {
+            (a, b) = (level[a], level[b]);
+        }
+    }
+    ancestors[0][a]
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Brick, Point, Stack};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+    fn example() -> Stack {
+        Stack::settle(EXAMPLE.lines().map(|line| line.parse::<Brick>().unwrap()))
+    }
+
+    #[test]
+    fn parses_bricks() {
+        let brick: Brick = "2,0,5~2,2,5".parse().unwrap();
+        assert_eq!(brick.start, Point { x: 2, y: 0, z: 5 });
+        assert_eq!(brick.footprint().count(), 3);
+        assert_eq!(brick.lowered_to(1).cubes().map(|p| p.z).max(), Some(1));
+        assert_eq!("1,1,8~1,1,9".parse::<Brick>().unwrap().height(), 2);
+        assert!("1,1~1,1,9".parse::<Brick>().is_err());
+        assert!("1,1,8".parse::<Brick>().is_err());
+    }
+
+    #[test]
+    fn settles_bricks() {
+        let stack = example();
+        let bottoms: Vec<isize> = stack.bricks().iter().map(Brick::bottom).collect();
+        assert_eq!(bottoms, vec![1, 2, 2, 3, 3, 4, 5]);
+        assert_eq!(stack.supports(0), &[1, 2]);
+        assert_eq!(stack.supported_by(3), &[1, 2]);
+        assert_eq!(stack.supported_by(0), &[] as &[usize]);
+
+        let removable: Vec<usize> = (0..stack.len())
+            .filter(|id| stack.is_removable(*id))
+            .collect();
+        assert_eq!(removable, vec![1, 2, 3, 4, 6]);
+    }
+
+    #[test]
+    fn finds_falling_bricks() {
+        let stack = example();
+        assert_eq!(stack.falling(&[0]), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(stack.falling(&[1, 2]), vec![3, 4, 5, 6]);
+        assert_eq!(stack.falling(&[3]), Vec::<usize>::new());
+        assert_eq!(stack.falling(&[3, 4]), vec![5, 6]);
+        assert_eq!(stack.falling(&[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn builds_dominator_tree() {
+        let tree = example().dominator_tree();
+        let idoms: Vec<Option<usize>> = (0..7).map(|id| tree.idom(id)).collect();
+        assert_eq!(
+            idoms,
+            vec![None, Some(0), Some(0), Some(0), Some(0), Some(0), Some(5)]
+        );
+
+        let chain_reactions: Vec<usize> = (0..7).map(|id| tree.chain_reaction(id)).collect();
+        assert_eq!(chain_reactions, vec![6, 0, 0, 0, 0, 1, 0]);
+    }
+
+    fn bricks_strategy() -> impl Strategy<Value = Vec<Brick>> {
+        let brick = (0..4_isize, 0..4_isize, 1..30_isize, 0..3_usize, 0..3_isize).prop_map(
+            |(x, y, z, axis, len)| {
+                let start = Point { x, y, z };
+                let mut end = start;
+                match axis {
+                    0 => end.x += len,
+                    1 => end.y += len,
+                    _ => end.z += len,
+                }
+                Brick { start, end }
+            },
+        );
+        prop::collection::vec(brick, 0..40)
+    }
+
+    proptest! {
+        #[test]
+        fn chain_reactions_match_falling_bricks(bricks in bricks_strategy()) {
+            let stack = Stack::settle(bricks);
+            let tree = stack.dominator_tree();
+            for id in 0..stack.len() {
+                prop_assert_eq!(tree.chain_reaction(id), stack.falling(&[id]).len());
+                prop_assert_eq!(stack.is_removable(id), stack.falling(&[id]).is_empty());
+            }
+        }
+    }
+}
@@ -0,0 +1,352 @@
+//! Calibration values of day 1: the first and the last digit of a line, where digits may also be
+//! spelled out as words from a configurable table.
+//!
+//! A [`Scanner`] compiles its word table into two Aho–Corasick automata, one over the words and
+//! one over the reversed words. The first match is found by scanning the line from the front, the
+//! last match by scanning it from the back, so overlapping words like `oneight` need no rewriting
+//! of the input and nothing is allocated per line.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Range;
+
+use crate::Answer;
+
+/// The numeric digits.
+pub const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+/// Digits spelled out in English, as in part two.
+pub const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// Occurrence of a word in a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// Byte range of the word in the line.
+    pub range: Range<usize>,
+    pub value: u32,
+}
+
+/// Finds the first and the last word of a table in a line.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    forward: Automaton,
+    backward: Automaton,
+}
+
+impl Scanner {
+    /// A scanner for `words` and their values. Fails on empty or duplicate words.
+    pub fn new(words: &[(&str, u32)]) -> Result<Self, CalibrationError> {
+        let reversed: Vec<(Vec<u8>, u32)> = words
+            .iter()
+            .map(|(word, value)| (word.bytes().rev().collect(), *value))
+            .collect();
+        Ok(Self {
+            forward: Automaton::new(words.iter().map(|(word, value)| (word.as_bytes(), *value)))?,
+            backward: Automaton::new(reversed.iter().map(|(word, value)| (&word[..], *value)))?,
+        })
+    }
+
+    /// A scanner for the numeric digits only, as in part one.
+    #[must_use]
+    pub fn digits() -> Self {
+        Self::new(&DIGITS).expect("the digits are distinct")
+    }
+
+    /// A scanner for the numeric digits and the spelled out `words`, e.g. [`ENGLISH`].
+    pub fn spelled(words: &[(&str, u32)]) -> Result<Self, CalibrationError> {
+        Self::new(&[&DIGITS[..], words].concat())
+    }
+
+    /// The match that starts first, preferring the longer word if several start there.
+    #[must_use]
+    pub fn first(&self, line: &str) -> Option<Match> {
+        let (start, len, value) = self.forward.leftmost(line.bytes())?;
+        Some(Match {
+            range: start..start + len,
+            value,
+        })
+    }
+
+    /// The match that ends last, preferring the longer word if several end there.
+    #[must_use]
+    pub fn last(&self, line: &str) -> Option<Match> {
+        let (start, len, value) = self.backward.leftmost(line.bytes().rev())?;
+        let end = line.len() - start;
+        Some(Match {
+            range: end - len..end,
+            value,
+        })
+    }
+
+    /// Ten times the value of the first match plus the value of the last match.
+    #[must_use]
+    pub fn calibration_value(&self, line: &str) -> Option<u32> {
+        Some(10 * self.first(line)?.value + self.last(line)?.value)
+    }
+
+    /// Sum of the calibration values of all lines, failing on the first line without a match.
+    pub fn calibration_sum(&self, input: &str) -> Result<Answer, CalibrationError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                self.calibration_value(line)
+                    .ok_or_else(|| CalibrationError(format!("line {} has no digit", i + 1)))
+            })
+            .sum()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+const ROOT: usize = 0;
+
+/// Aho–Corasick automaton with a complete transition table, so scanning never follows failure
+/// links.
+#[derive(Debug, Clone)]
+struct Automaton {
+    next: Vec<[u32; 256]>,
+    /// Length of the trie prefix every state stands for.
+    depth: Vec<usize>,
+    /// Length and value of the longest word that ends in a state.
+    output: Vec<Option<(usize, u32)>>,
+}
+
+impl Automaton {
+    fn new<'a>(words: impl Iterator<Item = (&'a [u8], u32)>) -> Result<Self, CalibrationError> {
+        let mut automaton = Self {
+            next: vec![[0; 256]],
+            depth: vec![0],
+            output: vec![None],
+        };
+
+        // the trie, where 0 marks a missing child since the root is nobody's child.
+        for (word, value) in words {
+            if word.is_empty() {
+                return Err(CalibrationError("words must not be empty".into()));
+            }
+            let mut state = ROOT;
+            for byte in word {
+                let child = automaton.next[state][usize::from(*byte)] as usize;
+                state = if child == ROOT {
+                    let child = automaton.next.len();
+                    automaton.next.push([0; 256]);
+                    automaton.depth.push(automaton.depth[state] + 1);
+                    automaton.output.push(None);
+                    automaton.next[state][usize::from(*byte)] = child as u32;
+                    child
+                } else {
+                    child
+                };
+            }
+            if automaton.output[state].is_some() {
+                return Err(CalibrationError(format!(
+                    "word `{}` is listed twice",
+                    String::from_utf8_lossy(word)
+                )));
+            }
+            automaton.output[state] = Some((word.len(), value));
+        }
+
+        // breadth first, so the failure state of every state is complete before its children
+        // need it. Missing transitions are copied from the failure state.
+        let mut fail = vec![ROOT; automaton.next.len()];
+        let mut queue: Vec<usize> = automaton.next[ROOT]
+            .iter()
+            .map(|child| *child as usize)
+            .filter(|child| *child != ROOT)
+            .collect();
+        let mut head = 0;
+        while let Some(&state) = queue.get(head) {
+            head += 1;
+            if automaton.output[state].is_none() {
+                automaton.output[state] = automaton.output[fail[state]];
+            }
+            for byte in 0..256 {
+                let child = automaton.next[state][byte] as usize;
+                let fallback = automaton.next[fail[state]][byte];
+                if child == ROOT {
+                    automaton.next[state][byte] = fallback;
+                } else {
+                    fail[child] = fallback as usize;
+                    queue.push(child);
+                }
+            }
+        }
+        Ok(automaton)
+    }
+
+    /// Start, length and value of the leftmost match in `bytes`, preferring the longest one.
+    fn leftmost(&self, bytes: impl Iterator<Item = u8>) -> Option<(usize, usize, u32)> {
+        let mut state = ROOT;
+        let mut best: Option<(usize, usize, u32)> = None;
+        for (i, byte) in bytes.enumerate() {
+            state = self.next[state][usize::from(byte)] as usize;
+            // the state is the longest suffix that may still grow into a word, so no match can
+            // start at or before the best one once it starts behind it.
+            if best.is_some_and(|(start, _, _)| i + 1 - self.depth[state] > start) {
+                break;
+            }
+            if let Some((len, value)) = self.output[state] {
+                let start = i + 1 - len;
+                if best.is_none_or(|(best_start, _, _)| start <= best_start) {
+                    best = Some((start, len, value));
+                }
+            }
+        }
+        best
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalibrationError(String);
+
+impl Error for CalibrationError {}
+
+impl Display for CalibrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{CalibrationError, Match, Scanner, ENGLISH};
+    use crate::Answer;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+    #[test]
+    fn scans_digits() {
+        let scanner = Scanner::digits();
+        assert_eq!(scanner.calibration_value("pqr3stu8vwx"), Some(38));
+        assert_eq!(scanner.calibration_value("treb7uchet"), Some(77));
+        assert_eq!(scanner.calibration_value("two1nine"), Some(11));
+        assert_eq!(scanner.calibration_value("eightwothree"), None);
+        assert_eq!(
+            scanner.calibration_sum(EXAMPLE),
+            Err(CalibrationError("line 2 has no digit".into()))
+        );
+    }
+
+    #[test]
+    fn scans_spelled_digits() {
+        let scanner = Scanner::spelled(&ENGLISH).unwrap();
+        let values: Vec<_> = EXAMPLE
+            .lines()
+            .map(|line| scanner.calibration_value(line))
+            .collect();
+        assert_eq!(values, [29, 83, 13, 24, 42, 14, 76].map(Some).to_vec());
+        assert_eq!(scanner.calibration_sum(EXAMPLE), Ok(Answer::from(281)));
+
+        // overlapping words share their letters.
+        assert_eq!(
+            scanner.first("zoneight"),
+            Some(Match {
+                range: 1..4,
+                value: 1
+            })
+        );
+        assert_eq!(
+            scanner.last("zoneight"),
+            Some(Match {
+                range: 3..8,
+                value: 8
+            })
+        );
+    }
+
+    #[test]
+    fn scans_other_words() {
+        let german = [
+            ("eins", 1),
+            ("zwei", 2),
+            ("drei", 3),
+            ("sieben", 7),
+            ("acht", 8),
+        ];
+        let scanner = Scanner::spelled(&german).unwrap();
+        assert_eq!(scanner.calibration_value("xzweinsiebenx"), Some(27));
+        assert_eq!(scanner.calibration_value("acht"), Some(88));
+        assert_eq!(scanner.calibration_value("one"), None);
+
+        // longer words win over the words they start or end with.
+        let scanner = Scanner::new(&[("ten", 10), ("tens", 11), ("s", 0)]).unwrap();
+        assert_eq!(scanner.first("tens").map(|m| m.value), Some(11));
+        assert_eq!(scanner.last("tens").map(|m| m.value), Some(11));
+
+        assert_eq!(
+            Scanner::new(&[("one", 1), ("one", 2)]).unwrap_err(),
+            CalibrationError("word `one` is listed twice".into())
+        );
+        assert!(Scanner::new(&[("", 1)]).is_err());
+    }
+
+    /// All occurrences of all words as `(start, end, value)`.
+    fn occurrences(words: &[(String, u32)], line: &str) -> Vec<(usize, usize, u32)> {
+        let mut found = Vec::new();
+        for (word, value) in words {
+            for start in 0..line.len() {
+                if line[start..].starts_with(word.as_str()) {
+                    found.push((start, start + word.len(), *value));
+                }
+            }
+        }
+        found
+    }
+
+    proptest! {
+        #[test]
+        fn matches_brute_force(
+            words in prop::collection::hash_map("[ab]{1,4}", 0_u32..100, 1..6),
+            line in "[abc]{0,20}",
+        ) {
+            let words: Vec<(String, u32)> = words.into_iter().collect();
+            let table: Vec<(&str, u32)> = words.iter().map(|(w, v)| (w.as_str(), *v)).collect();
+            let scanner = Scanner::new(&table).unwrap();
+            let found = occurrences(&words, &line);
+
+            let first = found
+                .iter()
+                .min_by_key(|(start, end, _)| (*start, std::cmp::Reverse(*end)))
+                .map(|(start, end, value)| Match { range: *start..*end, value: *value });
+            let last = found
+                .iter()
+                .max_by_key(|(start, end, _)| (*end, std::cmp::Reverse(*start)))
+                .map(|(start, end, value)| Match { range: *start..*end, value: *value });
+            prop_assert_eq!(scanner.first(&line), first);
+            prop_assert_eq!(scanner.last(&line), last);
+        }
+    }
+}
@@ -0,0 +1,414 @@
+//! Ranking of Camel Cards hands (day 7) under configurable rules.
+//!
+//! [`Rules`] define the order of the card labels, which labels are wild and which hand categories
+//! count, from weakest to strongest. Hands are classified by counting cards in a fixed-size array
+//! indexed by card rank. Wild cards join the two largest groups, split in whichever way fits the
+//! strongest category: all of them join the largest group for most categories, but with a reduced
+//! set of categories, e.g. a two pair may beat a three of a kind that does not count.
+use std::error::Error;
+use std::fmt::Display;
+
+/// Most distinct card labels a rule set can have.
+pub const MAX_RANKS: usize = 16;
+
+/// Number of cards in a hand.
+pub const HAND_SIZE: usize = 5;
+
+/// Hand categories, ordered from weakest to strongest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeKind,
+    FullHouse,
+    FourKind,
+    FiveKind,
+}
+
+impl Category {
+    /// All categories from weakest to strongest.
+    pub const ALL: [Category; 7] = [
+        Category::HighCard,
+        Category::OnePair,
+        Category::TwoPair,
+        Category::ThreeKind,
+        Category::FullHouse,
+        Category::FourKind,
+        Category::FiveKind,
+    ];
+
+    /// Whether a hand whose two largest groups of equal cards have `first` and `second` cards
+    /// contains this category.
+    #[must_use]
+    pub fn fits(self, first: u8, second: u8) -> bool {
+        match self {
+            Category::HighCard => true,
+            Category::OnePair => first >= 2,
+            Category::TwoPair => first >= 2 && second >= 2,
+            Category::ThreeKind => first >= 3,
+            Category::FullHouse => first >= 3 && second >= 2,
+            Category::FourKind => first >= 4,
+            Category::FiveKind => first >= 5,
+        }
+    }
+}
+
+/// A hand classified under some [`Rules`]. Hands order by category first and then card by card,
+/// which is only meaningful for hands of the same rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hand {
+    /// Position of the category in [`Rules::categories`] plus one, zero if none fits.
+    strength: u8,
+    /// Ranks of the cards in their original order.
+    ranks: [u8; HAND_SIZE],
+}
+
+impl Hand {
+    /// The ranks of the cards, indexes into the card order of the rules.
+    #[must_use]
+    pub fn ranks(&self) -> [u8; HAND_SIZE] {
+        self.ranks
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    labels: Vec<char>,
+    wild: [bool; MAX_RANKS],
+    categories: Vec<Category>,
+}
+
+impl Rules {
+    /// Cards `2` to `A` without wild cards, all categories.
+    #[must_use]
+    pub fn standard() -> Self {
+        Rules::new("23456789TJQKA", "", &Category::ALL).unwrap()
+    }
+
+    /// Jacks become jokers, which are wild but the weakest card on their own.
+    #[must_use]
+    pub fn jokers_wild() -> Self {
+        Rules::new("J23456789TQKA", "J", &Category::ALL).unwrap()
+    }
+
+    /// Rules with the cards of `order` from weakest to strongest, the cards of `wild` standing in
+    /// for any other card and the hand `categories` that count, from weakest to strongest. Hands
+    /// fall back to the strongest category they contain, e.g. a full house to three of a kind.
+    pub fn new(order: &str, wild: &str, categories: &[Category]) -> Result<Self, CardsError> {
+        let labels: Vec<char> = order.chars().collect();
+        if labels.len() > MAX_RANKS {
+            return Err(CardsError(format!(
+                "at most {MAX_RANKS} card labels are supported, got {}",
+                labels.len()
+            )));
+        }
+        if let Some((i, label)) = labels
+            .iter()
+            .enumerate()
+            .find(|(i, label)| labels[..*i].contains(label))
+        {
+            return Err(CardsError(format!(
+                "card `{label}` is ordered twice, at {i}"
+            )));
+        }
+        if categories.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(CardsError(
+                "categories must be distinct and ordered from weakest to strongest".into(),
+            ));
+        }
+
+        let mut rules = Rules {
+            labels,
+            wild: [false; MAX_RANKS],
+            categories: categories.to_vec(),
+        };
+        for label in wild.chars() {
+            let rank = rules.rank(label)?;
+            rules.wild[rank as usize] = true;
+        }
+        Ok(rules)
+    }
+
+    /// The hand categories from weakest to strongest.
+    #[must_use]
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    fn rank(&self, label: char) -> Result<u8, CardsError> {
+        self.labels
+            .iter()
+            .position(|l| *l == label)
+            .map(|rank| rank as u8)
+            .ok_or_else(|| CardsError(format!("unknown card `{label}`")))
+    }
+
+    /// The strongest category the ranks contain, [`None`] if no category of the rules fits.
+    #[must_use]
+    pub fn classify(&self, ranks: &[u8; HAND_SIZE]) -> Option<Category> {
+        self.strength(ranks)
+            .checked_sub(1)
+            .map(|i| self.categories[i as usize])
+    }
+
+    fn strength(&self, ranks: &[u8; HAND_SIZE]) -> u8 {
+        let mut counts = [0_u8; MAX_RANKS];
+        let mut wild = 0;
+        for rank in ranks {
+            if self.wild[*rank as usize] {
+                wild += 1;
+            } else {
+                counts[*rank as usize] += 1;
+            }
+        }
+
+        let (mut first, mut second) = (0, 0);
+        for count in counts {
+            if count > first {
+                (first, second) = (count, first);
+            } else if count > second {
+                second = count;
+            }
+        }
+
+        self.categories
+            .iter()
+            .rposition(|category| {
+                (0..=wild).any(|joined| {
+                    let (a, b) = (first + joined, second + wild - joined);
+                    category.fits(a.max(b), a.min(b))
+                })
+            })
+            .map_or(0, |i| i as u8 + 1)
+    }
+
+    /// Parses and classifies a hand like `KTJJT`.
+    pub fn hand(&self, cards: &str) -> Result<Hand, CardsError> {
+        let ranks: Vec<u8> = cards
+            .chars()
+            .map(|label| self.rank(label))
+            .collect::<Result<_, _>>()?;
+        let ranks: [u8; HAND_SIZE] = ranks
+            .try_into()
+            .map_err(|_| CardsError(format!("a hand has {HAND_SIZE} cards, got `{cards}`")))?;
+        Ok(Hand {
+            strength: self.strength(&ranks),
+            ranks,
+        })
+    }
+}
+
+/// An error which can be returned when building rules or parsing a hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardsError(String);
+
+impl Error for CardsError {}
+
+impl Display for CardsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{CardsError, Category, Rules, HAND_SIZE};
+
+    /// All multisets of `HAND_SIZE` ranks out of `ranks`, in non-decreasing order.
+    fn multisets(ranks: u8) -> Vec<[u8; HAND_SIZE]> {
+        let mut hands = vec![];
+        let mut hand = [0; HAND_SIZE];
+        fn fill(i: usize, min: u8, ranks: u8, hand: &mut [u8; HAND_SIZE], out: &mut Vec<[u8; 5]>) {
+            if i == HAND_SIZE {
+                out.push(*hand);
+                return;
+            }
+            for rank in min..ranks {
+                hand[i] = rank;
+                fill(i + 1, rank, ranks, hand, out);
+            }
+        }
+        fill(0, 0, ranks, &mut hand, &mut hands);
+        hands
+    }
+
+    /// Category from the sorted group sizes, without wild cards.
+    fn category_of(ranks: &[u8]) -> Category {
+        let mut groups: Vec<usize> = ranks
+            .iter()
+            .map(|r| ranks.iter().filter(|c| *c == r).count())
+            .collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        match groups.as_slice() {
+            [5, ..] => Category::FiveKind,
+            [4, ..] => Category::FourKind,
+            [3, 3, 3, 2, 2] => Category::FullHouse,
+            [3, ..] => Category::ThreeKind,
+            [2, 2, 2, 2, 1] => Category::TwoPair,
+            [2, ..] => Category::OnePair,
+            _ => Category::HighCard,
+        }
+    }
+
+    /// Categories a hand of `category` contains, e.g. a full house contains a three of a kind.
+    fn contained(category: Category) -> &'static [Category] {
+        use Category::*;
+        match category {
+            HighCard => &[HighCard],
+            OnePair => &[HighCard, OnePair],
+            TwoPair => &[HighCard, OnePair, TwoPair],
+            ThreeKind => &[HighCard, OnePair, ThreeKind],
+            FullHouse => &[HighCard, OnePair, TwoPair, ThreeKind, FullHouse],
+            FourKind => &[HighCard, OnePair, ThreeKind, FourKind],
+            FiveKind => &[HighCard, OnePair, ThreeKind, FourKind, FiveKind],
+        }
+    }
+
+    /// Strongest of `categories` over all replacements of the wild cards. Labels that are not in
+    /// the hand are interchangeable, so a wild card only has to try the labels of the other cards
+    /// and a few fresh ones, marked from 100 on so they are not wild themselves.
+    fn best_replacement(
+        ranks: &[u8; HAND_SIZE],
+        wild: &[u8],
+        categories: &[Category],
+    ) -> Option<Category> {
+        let Some(i) = ranks.iter().position(|r| wild.contains(r)) else {
+            let contained = contained(category_of(ranks));
+            return categories
+                .iter()
+                .rev()
+                .copied()
+                .find(|c| contained.contains(c));
+        };
+        let present = ranks.iter().copied().filter(|r| !wild.contains(r));
+        present
+            .chain(100..100 + HAND_SIZE as u8)
+            .map(|replacement| {
+                let mut replaced = *ranks;
+                replaced[i] = replacement;
+                best_replacement(&replaced, wild, categories)
+            })
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn classifies_all_standard_hands() {
+        let rules = Rules::standard();
+        let hands = multisets(13);
+        assert_eq!(hands.len(), 6188);
+        for hand in hands {
+            assert_eq!(rules.classify(&hand), Some(category_of(&hand)), "{hand:?}");
+        }
+    }
+
+    #[test]
+    fn classifies_all_hands_with_wild_cards() {
+        use Category::*;
+        let category_sets: [&[Category]; 5] = [
+            &Category::ALL,
+            &[OnePair, TwoPair],
+            &[OnePair, ThreeKind, FourKind],
+            &[HighCard, TwoPair, FullHouse],
+            &[TwoPair, FiveKind],
+        ];
+        for (order, wild) in [
+            ("J23456789TQKA", "J"),
+            ("23456789TJQKA", "JQ"),
+            ("23456789TJQKA", "2TA"),
+        ] {
+            let wild_ranks: Vec<u8> = wild
+                .chars()
+                .map(|w| order.chars().position(|c| c == w).unwrap() as u8)
+                .collect();
+            for categories in category_sets {
+                let rules = Rules::new(order, wild, categories).unwrap();
+                for hand in multisets(13) {
+                    assert_eq!(
+                        rules.classify(&hand),
+                        best_replacement(&hand, &wild_ranks, categories),
+                        "{order} with {wild} wild and {categories:?}, {hand:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ranks_hands() {
+        let rules = Rules::standard();
+        let hand = |cards: &str| rules.hand(cards).unwrap();
+        assert!(hand("33332") > hand("2AAAA"));
+        assert!(hand("77888") > hand("77788"));
+        assert!(hand("KK677") > hand("KTJJT"));
+        assert!(hand("32T3K") < hand("KTJJT"));
+
+        let jokers = Rules::jokers_wild();
+        let joker = |cards: &str| jokers.hand(cards).unwrap();
+        assert_eq!(
+            jokers.classify(&joker("QJJQ2").ranks()),
+            Some(Category::FourKind)
+        );
+        assert_eq!(
+            jokers.classify(&joker("JJJJJ").ranks()),
+            Some(Category::FiveKind)
+        );
+        assert!(joker("JKKK2") < joker("QQQQ2"));
+        assert!(joker("KTJJT") > joker("QQQJA"));
+    }
+
+    #[test]
+    fn supports_fewer_categories() {
+        // without full houses and two pairs, they count as three and one of a kind.
+        let rules = Rules::new(
+            "23456789TJQKA",
+            "",
+            &[Category::OnePair, Category::ThreeKind, Category::FourKind],
+        )
+        .unwrap();
+        let classify = |cards: &str| rules.classify(&rules.hand(cards).unwrap().ranks());
+        assert_eq!(classify("77888"), Some(Category::ThreeKind));
+        assert_eq!(classify("KK677"), Some(Category::OnePair));
+        assert_eq!(classify("AAAAA"), Some(Category::FourKind));
+        assert_eq!(classify("23456"), None);
+        assert!(rules.hand("23456").unwrap() < rules.hand("22345").unwrap());
+
+        // a wild card completes the second pair instead of a three of a kind that does not count.
+        let rules = Rules::new(
+            "23456789TJQKA",
+            "J",
+            &[Category::OnePair, Category::TwoPair],
+        )
+        .unwrap();
+        let classify = |cards: &str| rules.classify(&rules.hand(cards).unwrap().ranks());
+        assert_eq!(classify("AAKQJ"), Some(Category::TwoPair));
+        assert_eq!(classify("AKQJ2"), Some(Category::OnePair));
+    }
+
+    #[test]
+    fn rejects_invalid_rules_and_hands() {
+        let err = |cards: &str| Rules::standard().hand(cards).unwrap_err();
+        assert_eq!(
+            err("2345"),
+            CardsError("a hand has 5 cards, got `2345`".into())
+        );
+        assert_eq!(err("2345X"), CardsError("unknown card `X`".into()));
+        assert_eq!(
+            Rules::new("23452", "", &Category::ALL),
+            Err(CardsError("card `2` is ordered twice, at 4".into()))
+        );
+        assert_eq!(
+            Rules::new("234", "5", &Category::ALL),
+            Err(CardsError("unknown card `5`".into()))
+        );
+        assert!(Rules::new("0123456789ABCDEFG", "", &Category::ALL).is_err());
+        assert!(Rules::new("234", "", &[Category::OnePair, Category::OnePair]).is_err());
+        assert_eq!(
+            Rules::new("234", "", &[Category::FiveKind, Category::HighCard]),
+            Err(CardsError(
+                "categories must be distinct and ordered from weakest to strongest".into()
+            ))
+        );
+    }
+}
@@ -0,0 +1,458 @@
+//! Corridor graphs of hiking trail mazes (day 23), where long corridors without side branches are
+//! compressed into single weighted edges between junctions.
+//!
+//! Junctions get consecutive ids in reading order, so paths can be tracked with a `u64` bitmask
+//! instead of a set of positions. The longest simple path is found with a depth-first search over
+//! these masks, optionally with its branches spread over all cores.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::thread;
+
+/// Row and column of a tile.
+pub type Position = (usize, usize);
+
+/// Junctions compressed from a grid of `#` walls, `.` paths and `^>v<` slopes. The maze is entered
+/// through the open tile of the first row and left through the open tile of the last row.
+#[derive(Debug, Clone)]
+pub struct CorridorGraph {
+    positions: Vec<Position>,
+    edges: Vec<Vec<(usize, u32)>>,
+    directed: bool,
+    start: usize,
+    end: usize,
+}
+
+impl CorridorGraph {
+    /// Most junctions a graph can have, as paths are tracked in a `u64`.
+    pub const MAX_JUNCTIONS: usize = 64;
+
+    /// Compresses the maze in `input`. Slopes can only be walked downhill if `follow_slopes` is
+    /// set, otherwise they are treated as paths and every corridor can be walked both ways.
+    pub fn from_grid(input: &str, follow_slopes: bool) -> Result<Self, CorridorError> {
+        let maze = Maze {
+            grid: input.lines().map(str::as_bytes).collect(),
+            follow_slopes,
+        };
+        let grid = &maze.grid;
+
+        let entrance = |row: usize| {
+            let col = grid.get(row)?.iter().position(|tile| *tile != b'#')?;
+            Some((row, col))
+        };
+        let entrance_pos = entrance(0).ok_or(CorridorError::NoEntrance)?;
+        let exit_pos = grid
+            .len()
+            .checked_sub(1)
+            .and_then(entrance)
+            .filter(|exit| *exit != entrance_pos)
+            .ok_or(CorridorError::NoExit)?;
+
+        let positions: Vec<Position> = (0..grid.len())
+            .flat_map(|row| (0..grid[row].len()).map(move |col| (row, col)))
+            .filter(|pos| {
+                maze.open(*pos)
+                    && (*pos == entrance_pos
+                        || *pos == exit_pos
+                        || maze.neighbors(*pos).count() > 2)
+            })
+            .collect();
+        if positions.len() > Self::MAX_JUNCTIONS {
+            return Err(CorridorError::TooManyJunctions(positions.len()));
+        }
+        let ids: HashMap<Position, usize> = positions
+            .iter()
+            .enumerate()
+            .map(|(id, pos)| (*pos, id))
+            .collect();
+
+        let mut edges = vec![Vec::new(); positions.len()];
+        for (id, pos) in positions.iter().enumerate() {
+            for first in maze.moves(*pos) {
+                // corridor tiles have at most two open neighbors, so there is no choice but the
+                // way forward until the next junction or a dead end.
+                let (mut prev, mut current, mut length) = (*pos, first, 1);
+                let reached = loop {
+                    if let Some(next) = ids.get(&current) {
+                        break Some(*next);
+                    }
+                    let Some(next) = maze.moves(current).find(|next| *next != prev) else {
+                        break None;
+                    };
+                    (prev, current, length) = (current, next, length + 1);
+                };
+
+                match reached {
+                    Some(next) if next != id => {
+                        let edges: &mut Vec<(usize, u32)> = &mut edges[id];
+                        match edges.iter_mut().find(|(to, _)| *to == next) {
+                            Some((_, cost)) => *cost = (*cost).max(length),
+                            None => edges.push((next, length)),
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(CorridorGraph {
+            start: ids[&entrance_pos],
+            end: ids[&exit_pos],
+            positions,
+            edges,
+            directed: follow_slopes,
+        })
+    }
+
+    /// Number of junctions, including the entrance and exit.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    #[must_use]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    #[must_use]
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Whether corridors can only be walked one way. Otherwise every edge is listed from both ends.
+    #[must_use]
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    #[must_use]
+    pub fn position(&self, id: usize) -> Position {
+        self.positions[id]
+    }
+
+    /// Junctions reachable from `id` through a single corridor, with the corridor lengths.
+    #[must_use]
+    pub fn edges(&self, id: usize) -> &[(usize, u32)] {
+        &self.edges[id]
+    }
+
+    /// Length of the longest path from the entrance to the exit that visits no tile twice, if the
+    /// exit can be reached. With `parallel`, the first branches of the search are split over
+    /// threads.
+    #[must_use]
+    pub fn longest_path(&self, parallel: bool) -> Option<u32> {
+        let search = Search::new(self);
+        let start = (self.start, 1 << self.start, 0);
+        if !parallel {
+            let mut best = None;
+            search.extend(start, &mut best);
+            return best;
+        }
+
+        let threads = thread::available_parallelism().map_or(1, usize::from);
+        let (frontier, mut best) = search.frontier(start, threads * 16);
+        let frontier = &frontier;
+        let search = &search;
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|t| {
+                    scope.spawn(move || {
+                        let mut best = None;
+                        for state in frontier.iter().skip(t).step_by(threads) {
+                            search.extend(*state, &mut best);
+                        }
+                        best
+                    })
+                })
+                .collect();
+            for handle in handles {
+                best = best.max(handle.join().unwrap());
+            }
+        });
+        best
+    }
+}
+
+struct Maze<'a> {
+    grid: Vec<&'a [u8]>,
+    follow_slopes: bool,
+}
+
+impl Maze<'_> {
+    fn open(&self, (row, col): Position) -> bool {
+        self.grid
+            .get(row)
+            .and_then(|line| line.get(col))
+            .is_some_and(|tile| *tile != b'#')
+    }
+
+    fn neighbors(&self, (row, col): Position) -> impl Iterator<Item = Position> + '_ {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .filter_map(move |(dr, dc)| {
+                Some((row.checked_add_signed(dr)?, col.checked_add_signed(dc)?))
+            })
+            .filter(|next| self.open(*next))
+    }
+
+    /// Tiles that can be walked to from `pos`, slopes only allow going downhill.
+    fn moves(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        let downhill = match self.grid[pos.0][pos.1] {
+            b'^' => Some((-1, 0)),
+            b'v' => Some((1, 0)),
+            b'<' => Some((0, -1)),
+            b'>' => Some((0, 1)),
+            _ => None,
+        }
+        .filter(|_| self.follow_slopes);
+        self.neighbors(pos).filter(move |next| {
+            downhill.is_none_or(|(dr, dc)| {
+                pos.0.checked_add_signed(dr) == Some(next.0)
+                    && pos.1.checked_add_signed(dc) == Some(next.1)
+            })
+        })
+    }
+}
+
+/// A partial path: the last junction, the junctions visited so far and the length.
+type State = (usize, u64, u32);
+
+struct Search<'a> {
+    graph: &'a CorridorGraph,
+    /// If the exit can only be reached from a single junction, any path reaching that junction has
+    /// to continue to the exit, which prunes most of the search.
+    last: Option<(usize, u32)>,
+}
+
+impl<'a> Search<'a> {
+    fn new(graph: &'a CorridorGraph) -> Self {
+        let mut into_end = (0..graph.len()).flat_map(|id| {
+            graph
+                .edges(id)
+                .iter()
+                .filter(|(to, _)| *to == graph.end)
+                .map(move |(_, cost)| (id, *cost))
+        });
+        let last = match (into_end.next(), into_end.next()) {
+            (Some(last), None) => Some(last),
+            _ => None,
+        };
+        Search { graph, last }
+    }
+
+    /// The length of the path if it is complete.
+    fn finish(&self, (node, _, length): State) -> Option<u32> {
+        if node == self.graph.end {
+            return Some(length);
+        }
+        match self.last {
+            Some((last, cost)) if last == node => Some(length + cost),
+            _ => None,
+        }
+    }
+
+    fn successors(&self, (node, visited, length): State) -> impl Iterator<Item = State> + '_ {
+        self.graph
+            .edges(node)
+            .iter()
+            .filter(move |(next, _)| visited & (1 << next) == 0)
+            .map(move |(next, cost)| (*next, visited | 1 << next, length + cost))
+    }
+
+    fn extend(&self, state: State, best: &mut Option<u32>) {
+        if let Some(length) = self.finish(state) {
+            *best = (*best).max(Some(length));
+            return;
+        }
+        for next in self.successors(state) {
+            self.extend(next, best);
+        }
+    }
+
+    /// Expands the search breadth first until there are at least `size` partial paths left to
+    /// explore, returning them and the best complete path found on the way.
+    fn frontier(&self, start: State, size: usize) -> (Vec<State>, Option<u32>) {
+        let mut best = None;
+        let mut frontier = vec![start];
+        while !frontier.is_empty() && frontier.len() < size {
+            let mut next = Vec::new();
+            for state in frontier {
+                match self.finish(state) {
+                    Some(length) => best = best.max(Some(length)),
+                    None => next.extend(self.successors(state)),
+                }
+            }
+            frontier = next;
+        }
+        (frontier, best)
+    }
+}
+
+/// An error which can be returned when compressing a maze.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorridorError {
+    /// The first row has no open tile.
+    NoEntrance,
+    /// The last row has no open tile, or it is the entrance.
+    NoExit,
+    /// The maze has more junctions than [`CorridorGraph::MAX_JUNCTIONS`].
+    TooManyJunctions(usize),
+}
+
+impl Error for CorridorError {}
+
+impl Display for CorridorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CorridorError::NoEntrance => write!(f, "the first row has no open tile"),
+            CorridorError::NoExit => write!(f, "the last row has no open tile"),
+            CorridorError::TooManyJunctions(n) => write!(
+                f,
+                "{n} junctions exceed the limit of {}",
+                CorridorGraph::MAX_JUNCTIONS
+            ),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{CorridorError, CorridorGraph};
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    #[test]
+    fn compresses_corridors() {
+        let graph = CorridorGraph::from_grid(EXAMPLE, true).unwrap();
+        assert_eq!(graph.len(), 9);
+        assert_eq!((graph.start(), graph.end()), (0, 8));
+        assert_eq!(graph.position(graph.end()), (22, 21));
+        assert_eq!(graph.edges(graph.start()), &[(2, 15)]);
+        // slopes make the corridors one-way.
+        assert!(graph.edges(2).iter().all(|(to, _)| *to != 0));
+
+        let undirected = CorridorGraph::from_grid(EXAMPLE, false).unwrap();
+        assert!(!undirected.is_directed());
+        assert!(undirected.edges(2).contains(&(0, 15)));
+    }
+
+    #[test]
+    fn finds_longest_paths() {
+        let directed = CorridorGraph::from_grid(EXAMPLE, true).unwrap();
+        assert_eq!(directed.longest_path(false), Some(94));
+        assert_eq!(directed.longest_path(true), Some(94));
+
+        let undirected = CorridorGraph::from_grid(EXAMPLE, false).unwrap();
+        assert_eq!(undirected.longest_path(false), Some(154));
+        assert_eq!(undirected.longest_path(true), Some(154));
+
+        let blocked = CorridorGraph::from_grid("#.#\n#v#\n#^#\n#.#", true).unwrap();
+        assert_eq!(blocked.longest_path(false), None);
+        assert_eq!(blocked.longest_path(true), None);
+    }
+
+    #[test]
+    fn rejects_invalid_mazes() {
+        let err = |s: &str| CorridorGraph::from_grid(s, false).unwrap_err();
+        assert_eq!(err("###\n#.#"), CorridorError::NoEntrance);
+        assert_eq!(err("#.#\n###"), CorridorError::NoExit);
+        assert_eq!(err(""), CorridorError::NoEntrance);
+
+        // an open field, where every inner tile is a junction.
+        let mut field = vec![".".repeat(12); 12];
+        field[0] = format!(".{}", "#".repeat(11));
+        field[11] = format!("{}.", "#".repeat(11));
+        assert_eq!(err(&field.join("\n")), CorridorError::TooManyJunctions(120));
+    }
+
+    /// Longest path found by walking tile by tile.
+    fn brute_force(grid: &[Vec<u8>], follow_slopes: bool) -> Option<u32> {
+        let (rows, cols) = (grid.len(), grid[0].len());
+        let start = (0, grid[0].iter().position(|t| *t != b'#')?);
+        let end = (rows - 1, grid[rows - 1].iter().position(|t| *t != b'#')?);
+
+        let mut best = None;
+        let mut stack = vec![(start, 1_u64 << (start.0 * cols + start.1), 0)];
+        while let Some(((row, col), visited, length)) = stack.pop() {
+            if (row, col) == end {
+                best = best.max(Some(length));
+                continue;
+            }
+            let dirs: &[(isize, isize)] = match grid[row][col] {
+                b'^' if follow_slopes => &[(-1, 0)],
+                b'v' if follow_slopes => &[(1, 0)],
+                b'<' if follow_slopes => &[(0, -1)],
+                b'>' if follow_slopes => &[(0, 1)],
+                _ => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            };
+            for (dr, dc) in dirs {
+                let (Some(r), Some(c)) = (row.checked_add_signed(*dr), col.checked_add_signed(*dc))
+                else {
+                    continue;
+                };
+                let bit = 1 << (r * cols + c);
+                if r < rows && c < cols && grid[r][c] != b'#' && visited & bit == 0 {
+                    stack.push(((r, c), visited | bit, length + 1));
+                }
+            }
+        }
+        best
+    }
+
+    proptest! {
+        #[test]
+        fn matches_brute_force(
+            inner in prop::collection::vec(
+                prop::sample::select(vec![b'.', b'.', b'.', b'#', b'>', b'v', b'<', b'^']),
+                20,
+            ),
+            follow_slopes: bool,
+        ) {
+            // a 4x5 room behind walls, entered top left and left bottom right.
+            let mut grid = vec![b"#.#####".to_vec()];
+            for row in inner.chunks(5) {
+                grid.push([b"#", row, b"#"].concat());
+            }
+            grid.push(b"#####.#".to_vec());
+            let text = grid
+                .iter()
+                .map(|row| String::from_utf8_lossy(row))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let graph = CorridorGraph::from_grid(&text, follow_slopes).unwrap();
+            let expected = brute_force(&grid, follow_slopes);
+            prop_assert_eq!(graph.longest_path(false), expected);
+            prop_assert_eq!(graph.longest_path(true), expected);
+        }
+    }
+}
@@ -0,0 +1,403 @@
+//! Least heat loss routes for the crucibles of day 17, which have to move at least `min_run` and
+//! at most `max_run` blocks in a straight line before turning left or right.
+//!
+//! A route is searched over the states "arrived after a full run, moving horizontally or
+//! vertically", so every edge is a whole run followed by a turn. The heat loss of a run is at most
+//! `9 * max_run`, which bounds the spread of distances in the queue, so Dijkstra's algorithm runs
+//! on a circular bucket queue (Dial's algorithm) instead of a binary heap.
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Row and column of a block.
+pub type Position = (usize, usize);
+
+/// Straight-run limits of a crucible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crucible {
+    pub min_run: usize,
+    pub max_run: usize,
+}
+
+impl Crucible {
+    /// Panics unless `1 <= min_run <= max_run`.
+    #[must_use]
+    pub fn new(min_run: usize, max_run: usize) -> Self {
+        assert!(
+            1 <= min_run && min_run <= max_run,
+            "runs must satisfy 1 <= min <= max, got {min_run}..={max_run}"
+        );
+        Crucible { min_run, max_run }
+    }
+}
+
+/// A block the crucible moves into and the heat lost there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub position: Position,
+    pub heat: u8,
+}
+
+/// A cheapest route, the starting block is not part of `steps` as entering it loses no heat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub heat_loss: u32,
+    pub steps: Vec<Step>,
+}
+
+/// City blocks with the heat lost when entering each of them, parsed from rows of digits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeatMap {
+    rows: usize,
+    cols: usize,
+    heat: Vec<u8>,
+}
+
+impl HeatMap {
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[must_use]
+    pub fn heat(&self, (row, col): Position) -> u8 {
+        self.heat[row * self.cols + col]
+    }
+
+    /// The route from the top left block to `end` with the least heat loss, `end` defaults to the
+    /// bottom right block. The crucible may start moving in any direction, and has to complete its
+    /// last run on `end`. Returns [`None`] if `end` can not be reached.
+    #[must_use]
+    pub fn cheapest_route(&self, crucible: Crucible, end: Option<Position>) -> Option<Route> {
+        let end = end.unwrap_or((self.rows - 1, self.cols - 1));
+        if end.0 >= self.rows || end.1 >= self.cols {
+            return None;
+        }
+        if end == (0, 0) {
+            return Some(Route {
+                heat_loss: 0,
+                steps: Vec::new(),
+            });
+        }
+
+        // state `2 * block + axis`, where axis 0 arrived moving horizontally and 1 vertically.
+        let states = 2 * self.heat.len();
+        let mut distances = vec![u32::MAX; states];
+        let mut previous = vec![usize::MAX; states];
+        // a run loses at most 9 per block, so all queued distances lie within `size`.
+        let size = 9 * crucible.max_run + 1;
+        let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); size];
+        let mut queued = 0;
+        for state in [0, 1] {
+            distances[state] = 0;
+            buckets[0].push(state);
+            queued += 1;
+        }
+
+        let mut distance = 0;
+        while queued > 0 {
+            let bucket = distance as usize % size;
+            while let Some(state) = buckets[bucket].pop() {
+                queued -= 1;
+                if distances[state] != distance {
+                    // a cheaper way to this state was found after it was queued.
+                    continue;
+                }
+                let (block, axis) = (state / 2, state % 2);
+                let position = (block / self.cols, block % self.cols);
+                if position == end {
+                    return Some(self.route(state, &previous, distance));
+                }
+
+                // turn onto the other axis and move both ways along it.
+                let next_axis = 1 - axis;
+                for sign in [-1, 1] {
+                    let mut heat_loss = distance;
+                    for run in 1..=crucible.max_run {
+                        let Some(next) = self.offset(position, next_axis, sign, run) else {
+                            break;
+                        };
+                        heat_loss += u32::from(self.heat(next));
+                        if run < crucible.min_run {
+                            continue;
+                        }
+                        let next_state = 2 * (next.0 * self.cols + next.1) + next_axis;
+                        if heat_loss < distances[next_state] {
+                            distances[next_state] = heat_loss;
+                            previous[next_state] = state;
+                            buckets[heat_loss as usize % size].push(next_state);
+                            queued += 1;
+                        }
+                    }
+                }
+            }
+            distance += 1;
+        }
+        None
+    }
+
+    /// The block `run` blocks away from `position` along `axis` in the direction of `sign`.
+    fn offset(
+        &self,
+        (row, col): Position,
+        axis: usize,
+        sign: isize,
+        run: usize,
+    ) -> Option<Position> {
+        let delta = sign * run as isize;
+        let (row, col) = if axis == 0 {
+            (Some(row), col.checked_add_signed(delta))
+        } else {
+            (row.checked_add_signed(delta), Some(col))
+        };
+        let (row, col) = (row?, col?);
+        (row < self.rows && col < self.cols).then_some((row, col))
+    }
+
+    /// Walks the predecessors back from `state` and expands each run into its blocks.
+    fn route(&self, mut state: usize, previous: &[usize], heat_loss: u32) -> Route {
+        let position = |state: usize| (state / 2 / self.cols, state / 2 % self.cols);
+        let mut steps = Vec::new();
+        while previous[state] != usize::MAX {
+            let (from, to) = (position(previous[state]), position(state));
+            let mut block = to;
+            while block != from {
+                steps.push(Step {
+                    position: block,
+                    heat: self.heat(block),
+                });
+                block = (step_towards(block.0, from.0), step_towards(block.1, from.1));
+            }
+            state = previous[state];
+        }
+        steps.reverse();
+        Route { heat_loss, steps }
+    }
+}
+
+fn step_towards(from: usize, to: usize) -> usize {
+    match from.cmp(&to) {
+        Ordering::Less => from + 1,
+        Ordering::Equal => from,
+        Ordering::Greater => from - 1,
+    }
+}
+
+impl FromStr for HeatMap {
+    type Err = HeatMapFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.lines().filter(|line| !line.is_empty()).collect();
+        let cols = lines.first().ok_or(HeatMapFromStrError)?.len();
+        let mut heat = Vec::with_capacity(lines.len() * cols);
+        for line in &lines {
+            if line.len() != cols {
+                return Err(HeatMapFromStrError);
+            }
+            for c in line.chars() {
+                heat.push(c.to_digit(10).ok_or(HeatMapFromStrError)? as u8);
+            }
+        }
+        Ok(HeatMap {
+            rows: lines.len(),
+            cols,
+            heat,
+        })
+    }
+}
+
+/// An error which can be returned when parsing a heat map that is empty, not rectangular or
+/// contains something other than digits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeatMapFromStrError;
+
+impl Error for HeatMapFromStrError {}
+
+impl Display for HeatMapFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "heat map must be a non-empty rectangle of digits")
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Crucible, HeatMap, HeatMapFromStrError, Position};
+    use proptest::prelude::*;
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashMap};
+
+    const EXAMPLE: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    /// Lengths of the straight runs of a route, starting at the top left block.
+    fn runs(steps: &[Position]) -> Vec<usize> {
+        let mut runs: Vec<usize> = Vec::new();
+        let mut last = ((0, 0), None);
+        for position in steps {
+            let axis = Some(position.0 == last.0 .0);
+            if axis == last.1 {
+                *runs.last_mut().unwrap() += 1;
+            } else {
+                runs.push(1);
+            }
+            last = (*position, axis);
+        }
+        runs
+    }
+
+    #[test]
+    fn finds_cheapest_routes() {
+        let map: HeatMap = EXAMPLE.parse().unwrap();
+        let route = map.cheapest_route(Crucible::new(1, 3), None).unwrap();
+        assert_eq!(route.heat_loss, 102);
+        assert_eq!(route.steps.last().unwrap().position, (12, 12));
+        assert_eq!(
+            route.steps.iter().map(|s| u32::from(s.heat)).sum::<u32>(),
+            102
+        );
+        assert!(route.steps.iter().all(|s| s.heat == map.heat(s.position)));
+
+        let ultra = map.cheapest_route(Crucible::new(4, 10), None).unwrap();
+        assert_eq!(ultra.heat_loss, 94);
+        let positions: Vec<Position> = ultra.steps.iter().map(|s| s.position).collect();
+        assert!(runs(&positions).iter().all(|run| (4..=10).contains(run)));
+
+        let map: HeatMap = "111111111111\n999999999991\n999999999991\n999999999991\n999999999991"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            map.cheapest_route(Crucible::new(4, 10), None)
+                .unwrap()
+                .heat_loss,
+            71
+        );
+    }
+
+    #[test]
+    fn routes_to_any_end() {
+        let map: HeatMap = EXAMPLE.parse().unwrap();
+        let route = map
+            .cheapest_route(Crucible::new(1, 3), Some((0, 2)))
+            .unwrap();
+        assert_eq!(route.heat_loss, 5);
+        assert_eq!(route.steps.len(), 2);
+        assert_eq!(
+            map.cheapest_route(Crucible::new(1, 3), Some((0, 0)))
+                .unwrap()
+                .heat_loss,
+            0
+        );
+        assert!(map
+            .cheapest_route(Crucible::new(1, 3), Some((13, 0)))
+            .is_none());
+        // a run of at least 20 blocks does not fit the map.
+        assert!(map.cheapest_route(Crucible::new(20, 20), None).is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_maps() {
+        assert_eq!("".parse::<HeatMap>(), Err(HeatMapFromStrError));
+        assert_eq!("12\n1".parse::<HeatMap>(), Err(HeatMapFromStrError));
+        assert_eq!("1a".parse::<HeatMap>(), Err(HeatMapFromStrError));
+    }
+
+    /// Dijkstra over states of position, direction and length of the current run.
+    fn brute_force(map: &HeatMap, min_run: usize, max_run: usize) -> Option<u32> {
+        let end = (map.rows() - 1, map.cols() - 1);
+        let mut best: HashMap<(Position, usize, usize), u32> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        // direction 4 is standing at the start, any direction can be taken from there.
+        queue.push(Reverse((0, (0, 0), 4, 0)));
+        while let Some(Reverse((loss, position, dir, run))) = queue.pop() {
+            if position == end && run >= min_run {
+                return Some(loss);
+            }
+            if best.get(&(position, dir, run)).is_some_and(|b| *b < loss) {
+                continue;
+            }
+            for next_dir in 0..4 {
+                let turning = dir != next_dir;
+                if dir != 4 && (next_dir == (dir + 2) % 4 || (turning && run < min_run)) {
+                    continue;
+                }
+                let next_run = if turning { 1 } else { run + 1 };
+                if next_run > max_run {
+                    continue;
+                }
+                let (dr, dc) = [(0, 1), (1, 0), (0, -1), (-1, 0)][next_dir];
+                let (Some(row), Some(col)) = (
+                    position.0.checked_add_signed(dr),
+                    position.1.checked_add_signed(dc),
+                ) else {
+                    continue;
+                };
+                if row >= map.rows() || col >= map.cols() {
+                    continue;
+                }
+                let loss = loss + u32::from(map.heat((row, col)));
+                let key = ((row, col), next_dir, next_run);
+                if best.get(&key).is_none_or(|b| loss < *b) {
+                    best.insert(key, loss);
+                    queue.push(Reverse((loss, (row, col), next_dir, next_run)));
+                }
+            }
+        }
+        None
+    }
+
+    proptest! {
+        #[test]
+        fn matches_brute_force(
+            rows in 1_usize..7,
+            cols in 1_usize..7,
+            digits in prop::collection::vec(1_u8..=9, 36),
+            min_run in 1_usize..4,
+            extra in 0_usize..4,
+        ) {
+            let map: HeatMap = (0..rows)
+                .map(|row| {
+                    (0..cols)
+                        .map(|col| char::from(b'0' + digits[row * 6 + col]))
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+                .parse()
+                .unwrap();
+            let crucible = Crucible::new(min_run, min_run + extra);
+
+            let route = map.cheapest_route(crucible, None);
+            if (rows, cols) == (1, 1) {
+                prop_assert_eq!(route.as_ref().map(|r| r.heat_loss), Some(0));
+            } else {
+                prop_assert_eq!(
+                    route.as_ref().map(|r| r.heat_loss),
+                    brute_force(&map, min_run, min_run + extra)
+                );
+            }
+            if let Some(route) = route {
+                let positions: Vec<Position> = route.steps.iter().map(|s| s.position).collect();
+                prop_assert!(runs(&positions)
+                    .iter()
+                    .all(|run| (crucible.min_run..=crucible.max_run).contains(run)));
+            }
+        }
+    }
+}
@@ -132,9 +132,10 @@ macro_rules! day {
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
     use super::{all_days, Day};
+    use proptest::prelude::*;
 
     #[test]
     fn all_days_iterator() {
@@ -167,6 +168,44 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_are_ordered() {
+        let days: Vec<Day> = all_days().collect();
+        assert_eq!(days.len(), 25);
+        assert!(days.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(days, (1..=25).filter_map(Day::new).collect::<Vec<_>>());
+    }
+
+    proptest! {
+        #[test]
+        fn accepts_only_days_of_advent(n: u8) {
+            prop_assert_eq!(Day::new(n).is_some(), (1..=25).contains(&n));
+        }
+
+        #[test]
+        fn round_trips_through_string(n in 1_u8..=25) {
+            let day = Day::new(n).unwrap();
+            let s = day.to_string();
+            prop_assert_eq!(s.len(), 2);
+            prop_assert_eq!(s.parse::<Day>().unwrap(), day);
+            prop_assert_eq!(n.to_string().parse::<Day>().unwrap(), day);
+        }
+
+        #[test]
+        fn parses_like_u8(s in "[ +-]?[0-9]{0,4}[ a]?") {
+            let expected = s.parse::<u8>().ok().and_then(Day::new);
+            prop_assert_eq!(s.parse::<Day>().ok(), expected);
+        }
+
+        #[test]
+        fn is_ordered_like_u8(a in 1_u8..=25, b in 1_u8..=25) {
+            let (day_a, day_b) = (Day::new(a).unwrap(), Day::new(b).unwrap());
+            prop_assert_eq!(day_a.cmp(&day_b), a.cmp(&b));
+            prop_assert_eq!(day_a.partial_cmp(&b), Some(a.cmp(&b)));
+            prop_assert_eq!(day_a == b, a == b);
+        }
+    }
 }
 
 /* -------------------------------------------------------------------------- */
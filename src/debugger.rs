@@ -0,0 +1,377 @@
+//! Interactive stepping debugger for simulation days, launched with the `--step` flag of the
+//! solution binaries (`cargo solve <day> --step`).
+//!
+//! Days implement [`Simulation`] and register a constructor via
+//! `solution!(DAY, step: simulation)`. The debugger shows the grid view of the current state and
+//! a state panel, and is driven by single key presses:
+//!
+//! - `space` / `n` / `→`: advance one step
+//! - `b` / `←`: rewind one step
+//! - `r`: run / pause
+//! - `+` / `-`: change the number of steps per tick while running
+//! - `e`: run to the end
+//! - `q`: quit
+use std::{
+    collections::VecDeque,
+    env,
+    io::{self, IsTerminal, Read, Write},
+    process::{self, Command as Process},
+};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::viz::Frame;
+
+/// A step-by-step simulation that can be inspected with the debugger.
+/// States are cloned before every step to allow rewinding.
+pub trait Simulation: Clone {
+    /// Advances the simulation by one step. Returns `false` once the simulation has finished.
+    fn step(&mut self) -> bool;
+
+    /// Renders the current state as a grid.
+    fn frame(&self) -> Frame;
+
+    /// Named values shown in the state panel.
+    fn state(&self) -> Vec<(&'static str, String)>;
+}
+
+/// Number of states kept for rewinding, older states are dropped.
+const MAX_HISTORY: usize = 10_000;
+const MAX_SPEED: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Step,
+    Back,
+    ToggleRun,
+    Faster,
+    Slower,
+    End,
+    Quit,
+}
+
+/// Parses key presses read from a terminal in raw mode.
+#[must_use]
+pub fn parse_keys(bytes: &[u8]) -> Vec<Command> {
+    let mut commands = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        // arrow keys are sent as `ESC [ C` (right) and `ESC [ D` (left).
+        if bytes[i..].starts_with(b"\x1b[") && i + 2 < bytes.len() {
+            match bytes[i + 2] {
+                b'C' => commands.push(Command::Step),
+                b'D' => commands.push(Command::Back),
+                _ => {}
+            }
+            i += 3;
+            continue;
+        }
+
+        let command = match bytes[i] {
+            b' ' | b'n' => Some(Command::Step),
+            b'b' => Some(Command::Back),
+            b'r' => Some(Command::ToggleRun),
+            b'+' => Some(Command::Faster),
+            b'-' => Some(Command::Slower),
+            b'e' => Some(Command::End),
+            // ctrl-c arrives as a byte since signals are disabled in raw mode.
+            b'q' | 3 => Some(Command::Quit),
+            _ => None,
+        };
+        commands.extend(command);
+        i += 1;
+    }
+    commands
+}
+
+pub struct Debugger<S: Simulation> {
+    current: S,
+    history: VecDeque<S>,
+    steps: usize,
+    finished: bool,
+    running: bool,
+    speed: usize,
+}
+
+impl<S: Simulation> Debugger<S> {
+    pub fn new(simulation: S) -> Self {
+        Debugger {
+            current: simulation,
+            history: VecDeque::new(),
+            steps: 0,
+            finished: false,
+            running: false,
+            speed: 1,
+        }
+    }
+
+    #[must_use]
+    pub fn current(&self) -> &S {
+        &self.current
+    }
+
+    #[must_use]
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    pub fn step(&mut self) {
+        if self.finished {
+            return;
+        }
+        if self.history.len() == MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(self.current.clone());
+        self.finished = !self.current.step();
+        self.steps += 1;
+    }
+
+    pub fn back(&mut self) {
+        if let Some(previous) = self.history.pop_back() {
+            self.current = previous;
+            self.steps -= 1;
+            self.finished = false;
+        }
+    }
+
+    /// Advances by the current speed if running.
+    pub fn tick(&mut self) {
+        if !self.running {
+            return;
+        }
+        for _ in 0..self.speed {
+            self.step();
+        }
+        self.running = !self.finished;
+    }
+
+    pub fn apply(&mut self, command: Command) {
+        match command {
+            Command::Step => {
+                self.running = false;
+                self.step();
+            }
+            Command::Back => {
+                self.running = false;
+                self.back();
+            }
+            Command::ToggleRun => self.running = !self.running && !self.finished,
+            Command::Faster => self.speed = (self.speed * 2).min(MAX_SPEED),
+            Command::Slower => self.speed = (self.speed / 2).max(1),
+            Command::End => {
+                self.running = false;
+                while !self.finished {
+                    self.step();
+                }
+            }
+            Command::Quit => {}
+        }
+    }
+
+    /// Renders the grid view, the state panel and a help line.
+    #[must_use]
+    pub fn screen(&self) -> String {
+        let frame = self.current.frame();
+        let mut out = format!("{ANSI_BOLD}{}{ANSI_RESET}\n", frame.title);
+        out.push_str(&frame.to_ansi());
+        out.push('\n');
+
+        let status = if self.finished {
+            "finished"
+        } else if self.running {
+            "running"
+        } else {
+            "paused"
+        };
+        out.push_str(&format!(
+            "step {} ({status}, {} steps per tick)\n",
+            self.steps, self.speed
+        ));
+        for (name, value) in self.current.state() {
+            out.push_str(&format!("  {name}: {value}\n"));
+        }
+
+        out.push_str(&format!(
+            "\n{ANSI_ITALIC}space/n/→ step · b/← back · r run/pause · +/- speed · e end · q quit{ANSI_RESET}\n"
+        ));
+        out
+    }
+}
+
+/// Whether `--step` was passed to the binary.
+pub fn requested() -> bool {
+    env::args().any(|x| x == "--step")
+}
+
+/// Puts the terminal into raw mode, restoring it when dropped.
+/// Reads return after at most 100ms so that running simulations keep advancing.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enable() -> Option<Self> {
+        Process::new("stty")
+            .args(["-icanon", "-echo", "-isig", "min", "0", "time", "1"])
+            .status()
+            .ok()?
+            .success()
+            .then_some(RawTerminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = Process::new("stty").arg("sane").status();
+        print!("\x1b[?25h");
+        let _ = io::stdout().flush();
+    }
+}
+
+/// Runs the interactive debugger until the user quits.
+pub fn run<S: Simulation>(simulation: S) {
+    if !io::stdin().is_terminal() {
+        eprintln!("Error: --step requires an interactive terminal.");
+        process::exit(1);
+    }
+    let Some(_terminal) = RawTerminal::enable() else {
+        eprintln!("Error: could not put the terminal into raw mode using `stty`.");
+        process::exit(1);
+    };
+
+    let mut debugger = Debugger::new(simulation);
+    let mut stdin = io::stdin().lock();
+    let mut buffer = [0; 64];
+    let mut redraw = true;
+
+    loop {
+        if redraw {
+            // hide the cursor, clear the screen and draw from the top left corner.
+            print!("\x1b[?25l\x1b[2J\x1b[H{}", debugger.screen());
+            io::stdout().flush().unwrap();
+        }
+
+        let read = stdin.read(&mut buffer).unwrap_or(0);
+        let commands = parse_keys(&buffer[..read]);
+        if commands.contains(&Command::Quit) {
+            break;
+        }
+
+        redraw = !commands.is_empty() || debugger.is_running();
+        for command in commands {
+            debugger.apply(command);
+        }
+        debugger.tick();
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{parse_keys, Command, Debugger, Simulation};
+    use crate::viz::Frame;
+
+    #[derive(Clone)]
+    struct Counter {
+        value: usize,
+        limit: usize,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            self.value += 1;
+            self.value < self.limit
+        }
+
+        fn frame(&self) -> Frame {
+            Frame::from_text("counter", &"#".repeat(self.value.max(1)))
+        }
+
+        fn state(&self) -> Vec<(&'static str, String)> {
+            vec![("value", self.value.to_string())]
+        }
+    }
+
+    fn debugger(limit: usize) -> Debugger<Counter> {
+        Debugger::new(Counter { value: 0, limit })
+    }
+
+    #[test]
+    fn parses_keys() {
+        assert_eq!(
+            parse_keys(b"n b\x1b[C\x1b[Dxrq"),
+            vec![
+                Command::Step,
+                Command::Step,
+                Command::Back,
+                Command::Step,
+                Command::Back,
+                Command::ToggleRun,
+                Command::Quit
+            ]
+        );
+        assert_eq!(parse_keys(&[3]), vec![Command::Quit]);
+    }
+
+    #[test]
+    fn steps_and_rewinds() {
+        let mut debugger = debugger(3);
+        debugger.apply(Command::Step);
+        debugger.apply(Command::Step);
+        assert_eq!((debugger.current().value, debugger.steps()), (2, 2));
+
+        debugger.apply(Command::Back);
+        assert_eq!((debugger.current().value, debugger.steps()), (1, 1));
+
+        // rewinding past the start is a no-op.
+        debugger.apply(Command::Back);
+        debugger.apply(Command::Back);
+        assert_eq!((debugger.current().value, debugger.steps()), (0, 0));
+    }
+
+    #[test]
+    fn stops_when_finished() {
+        let mut debugger = debugger(3);
+        debugger.apply(Command::End);
+        assert!(debugger.is_finished());
+        assert_eq!(debugger.current().value, 3);
+
+        debugger.apply(Command::Step);
+        assert_eq!(debugger.steps(), 3);
+
+        debugger.apply(Command::Back);
+        assert!(!debugger.is_finished());
+    }
+
+    #[test]
+    fn runs_at_speed() {
+        let mut debugger = debugger(10);
+        debugger.apply(Command::Faster);
+        debugger.apply(Command::Faster);
+        debugger.apply(Command::ToggleRun);
+        debugger.tick();
+        assert_eq!(debugger.current().value, 4);
+
+        debugger.tick();
+        debugger.tick();
+        assert_eq!(debugger.current().value, 10);
+        assert!(!debugger.is_running());
+    }
+
+    #[test]
+    fn renders_screen() {
+        let mut debugger = debugger(3);
+        debugger.apply(Command::Step);
+        let screen = debugger.screen();
+        assert!(screen.contains("counter"));
+        assert!(screen.contains("step 1 (paused, 1 steps per tick)"));
+        assert!(screen.contains("  value: 1"));
+    }
+}
@@ -0,0 +1,341 @@
+//! Differential testing of optimized solutions against slow but obviously correct reference
+//! implementations ("oracles"), e.g. a simulation or a flood fill.
+//!
+//! Both are run on inputs from [`crate::gen`], from the smallest size up. On the first mismatch
+//! the input is shrunk as long as it keeps failing and the smallest failing input is reported:
+//!
+//! ```ignore
+//! Differential::new(DAY, ("part_one", part_one), ("flood_fill", flood_fill))
+//!     .sizes(1..=20)
+//!     .shrink(differential::shrink_lines)
+//!     .run();
+//! ```
+use std::any::Any;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::gen::generate;
+use crate::Day;
+
+/// Answer of a solution, or the message it panicked with.
+type Outcome<T> = Result<T, String>;
+
+type Shrinker = Box<dyn Fn(&str) -> Vec<String>>;
+type Filter = Box<dyn Fn(&str) -> bool>;
+
+pub struct Differential<C, O> {
+    day: Day,
+    candidate: (&'static str, C),
+    oracle: (&'static str, O),
+    sizes: RangeInclusive<usize>,
+    seeds: u64,
+    shrink: Shrinker,
+    filter: Filter,
+}
+
+/// A failing input after shrinking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch<T> {
+    pub size: usize,
+    pub seed: u64,
+    pub input: String,
+    pub candidate: Outcome<T>,
+    pub oracle: Outcome<T>,
+    pub shrink_steps: usize,
+}
+
+impl<C, O, T> Differential<C, O>
+where
+    C: Fn(&str) -> T,
+    O: Fn(&str) -> T,
+    T: PartialEq + Debug,
+{
+    /// Compares `candidate` with `oracle` on generated inputs of sizes 1 to 10, using 5 seeds per
+    /// size. Inputs are not shrunk by default.
+    pub fn new(day: Day, candidate: (&'static str, C), oracle: (&'static str, O)) -> Self {
+        Differential {
+            day,
+            candidate,
+            oracle,
+            sizes: 1..=10,
+            seeds: 5,
+            shrink: Box::new(|_| Vec::new()),
+            filter: Box::new(|_| true),
+        }
+    }
+
+    /// Sizes of the generated inputs. Keep them small enough for the oracle.
+    #[must_use]
+    pub fn sizes(mut self, sizes: RangeInclusive<usize>) -> Self {
+        self.sizes = sizes;
+        self
+    }
+
+    /// Number of seeds tried per size.
+    #[must_use]
+    pub fn seeds(mut self, seeds: u64) -> Self {
+        self.seeds = seeds;
+        self
+    }
+
+    /// Sets the function that proposes smaller variants of a failing input, e.g. [`shrink_lines`].
+    #[must_use]
+    pub fn shrink(mut self, shrink: impl Fn(&str) -> Vec<String> + 'static) -> Self {
+        self.shrink = Box::new(shrink);
+        self
+    }
+
+    /// Only compares on inputs accepted by `filter`, e.g. the day's validation function.
+    /// Shrunk inputs that are rejected are skipped.
+    #[must_use]
+    pub fn filter(mut self, filter: impl Fn(&str) -> bool + 'static) -> Self {
+        self.filter = Box::new(filter);
+        self
+    }
+
+    fn outcomes(&self, input: &str) -> Option<(Outcome<T>, Outcome<T>)> {
+        let candidate = catch(|| (self.candidate.1)(input));
+        let oracle = catch(|| (self.oracle.1)(input));
+        (candidate != oracle).then_some((candidate, oracle))
+    }
+
+    /// Returns the smallest failing input found, if any.
+    pub fn find_mismatch(&self) -> Option<Mismatch<T>> {
+        let (size, seed, mut input, (mut candidate, mut oracle)) =
+            self.sizes.clone().find_map(|size| {
+                (0..self.seeds).find_map(|seed| {
+                    let input = generate(self.day, size, seed).unwrap_or_else(|e| {
+                        panic!("failed to generate an input of size {size}: {e}")
+                    });
+                    if !(self.filter)(&input) {
+                        return None;
+                    }
+                    let outcomes = self.outcomes(&input)?;
+                    Some((size, seed, input, outcomes))
+                })
+            })?;
+
+        // greedily take the first smaller input that still fails until none does.
+        let mut shrink_steps = 0;
+        while let Some((smaller, outcomes)) = (self.shrink)(&input).into_iter().find_map(|s| {
+            if !(self.filter)(&s) {
+                return None;
+            }
+            let outcomes = self.outcomes(&s)?;
+            Some((s, outcomes))
+        }) {
+            input = smaller;
+            (candidate, oracle) = outcomes;
+            shrink_steps += 1;
+        }
+
+        Some(Mismatch {
+            size,
+            seed,
+            input,
+            candidate,
+            oracle,
+            shrink_steps,
+        })
+    }
+
+    /// Panics with a report of the smallest failing input if `candidate` and `oracle` disagree.
+    pub fn run(&self) {
+        let Some(mismatch) = self.find_mismatch() else {
+            return;
+        };
+
+        panic!(
+            "day {}: {} and {} disagree on the generated input of size {} and seed {} \
+             (shrunk {} times):\n{}\n{}: {:?}\n{}: {:?}",
+            self.day,
+            self.candidate.0,
+            self.oracle.0,
+            mismatch.size,
+            mismatch.seed,
+            mismatch.shrink_steps,
+            mismatch.input,
+            self.candidate.0,
+            mismatch.candidate,
+            self.oracle.0,
+            mismatch.oracle
+        );
+    }
+}
+
+fn catch<T>(func: impl FnOnce() -> T) -> Outcome<T> {
+    panic::catch_unwind(AssertUnwindSafe(func)).map_err(|e| panic_message(e.as_ref()))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("panicked: {message}")
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("panicked: {message}")
+    } else {
+        "panicked".into()
+    }
+}
+
+/// Proposes the input with one line removed, for every line.
+#[must_use]
+pub fn shrink_lines(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    (0..lines.len())
+        .map(|skip| {
+            let kept: Vec<&str> = (0..lines.len())
+                .filter(|i| *i != skip)
+                .map(|i| lines[i])
+                .collect();
+            kept.join("\n")
+        })
+        .collect()
+}
+
+/// Proposes the grid with one row or one column removed.
+#[must_use]
+pub fn shrink_grid(input: &str) -> Vec<String> {
+    let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let cols = grid.first().map_or(0, Vec::len);
+
+    let rows = shrink_lines(input);
+    let columns = (0..cols).map(|skip| {
+        grid.iter()
+            .map(|line| {
+                line.iter()
+                    .enumerate()
+                    .filter(|(col, _)| *col != skip)
+                    .map(|(_, c)| c)
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+    rows.into_iter().chain(columns).collect()
+}
+
+/// Proposes the grid with one cell replaced by `blank`, for every cell that isn't blank yet.
+#[must_use]
+pub fn blank_cells(input: &str, blank: char) -> Vec<String> {
+    input
+        .char_indices()
+        .filter(|(_, c)| *c != blank && *c != '\n')
+        .map(|(i, c)| {
+            let mut blanked = input.to_string();
+            blanked.replace_range(i..i + c.len_utf8(), &blank.to_string());
+            blanked
+        })
+        .collect()
+}
+
+/// Proposes the input with one whitespace-separated field removed from every line, keeping the
+/// leading label of each line. Used for column-based inputs like the races of day 6.
+#[must_use]
+pub fn shrink_fields(input: &str) -> Vec<String> {
+    let rows: Vec<Vec<&str>> = input
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    let fields = rows.iter().map(Vec::len).min().unwrap_or(0);
+    // keep the label and at least one value.
+    if fields <= 2 {
+        return Vec::new();
+    }
+
+    (1..fields)
+        .map(|skip| {
+            rows.iter()
+                .map(|row| {
+                    let kept: Vec<&str> = (0..row.len())
+                        .filter(|i| *i != skip)
+                        .map(|i| row[i])
+                        .collect();
+                    kept.join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{blank_cells, shrink_fields, shrink_grid, shrink_lines, Differential};
+    use crate::day;
+
+    fn lines(input: &str) -> usize {
+        input.lines().count()
+    }
+
+    #[test]
+    fn passes_on_agreement() {
+        let differential = Differential::new(
+            day!(9),
+            ("lines", lines),
+            ("count", |input: &str| input.split('\n').count()),
+        );
+        assert_eq!(differential.find_mismatch(), None);
+        differential.run();
+    }
+
+    #[test]
+    fn shrinks_mismatch() {
+        // disagrees on every input with at least three lines.
+        let capped = |input: &str| lines(input).min(2);
+        let mismatch = Differential::new(day!(9), ("lines", lines), ("capped", capped))
+            .shrink(shrink_lines)
+            .find_mismatch()
+            .unwrap();
+        assert_eq!(mismatch.size, 3);
+        assert_eq!(mismatch.seed, 0);
+        assert_eq!(lines(&mismatch.input), 3);
+        assert_eq!((mismatch.candidate, mismatch.oracle), (Ok(3), Ok(2)));
+        assert_eq!(mismatch.shrink_steps, 0);
+
+        let mismatch = Differential::new(day!(9), ("lines", lines), ("capped", capped))
+            .sizes(8..=8)
+            .shrink(shrink_lines)
+            .find_mismatch()
+            .unwrap();
+        assert_eq!(lines(&mismatch.input), 3);
+        assert_eq!(mismatch.shrink_steps, 5);
+    }
+
+    #[test]
+    fn reports_panics() {
+        let panics = |input: &str| -> usize {
+            assert!(lines(input) < 4, "too long");
+            lines(input)
+        };
+        let mismatch = Differential::new(day!(9), ("panics", panics), ("lines", lines))
+            .find_mismatch()
+            .unwrap();
+        assert_eq!(mismatch.size, 4);
+        assert!(mismatch.candidate.unwrap_err().contains("too long"));
+    }
+
+    #[test]
+    fn skips_filtered_inputs() {
+        let capped = |input: &str| lines(input).min(2);
+        let differential = Differential::new(day!(9), ("lines", lines), ("capped", capped))
+            .filter(|input| lines(input) <= 2);
+        assert_eq!(differential.find_mismatch(), None);
+    }
+
+    #[test]
+    fn shrinks_grids() {
+        assert_eq!(shrink_lines("a\nb\nc"), vec!["b\nc", "a\nc", "a\nb"]);
+        assert_eq!(shrink_grid("ab\ncd"), vec!["cd", "ab", "b\nd", "a\nc"]);
+        assert_eq!(blank_cells("#.\n.#", '.'), vec!["..\n.#", "#.\n.."]);
+    }
+
+    #[test]
+    fn shrinks_fields() {
+        assert_eq!(
+            shrink_fields("Time: 7 15\nDistance: 9 40"),
+            vec!["Time: 15\nDistance: 40", "Time: 7\nDistance: 9"]
+        );
+        assert!(shrink_fields("Time: 7\nDistance: 9").is_empty());
+    }
+}
@@ -0,0 +1,248 @@
+//! Graphviz DOT export for graph-shaped puzzles, enabled with the `--dot <path>` option of the
+//! solution binaries.
+//!
+//! Solutions export graphs via [`export`], the graph is only built if an output path was passed.
+//! The first graph of a run replaces the file, further graphs (e.g. one per part) are appended to
+//! it. Render the result with e.g. `dot -Tsvg -O graph.dot`.
+use std::{
+    env,
+    fmt::{Display, Write},
+    fs::{self, OpenOptions},
+    io::{self, Write as _},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+use crate::template::runner;
+
+/// A node or an edge together with its attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    ids: (String, Option<String>),
+    attrs: Vec<(String, String)>,
+}
+
+impl Element {
+    /// Sets an attribute such as `label`, `shape` or `color`.
+    pub fn attr(&mut self, key: &str, value: impl Display) -> &mut Self {
+        self.attrs.push((key.to_string(), value.to_string()));
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    name: String,
+    directed: bool,
+    attrs: Vec<(String, String)>,
+    nodes: Vec<Element>,
+    edges: Vec<Element>,
+}
+
+impl Graph {
+    fn new(name: &str, directed: bool) -> Self {
+        Graph {
+            name: name.to_string(),
+            directed,
+            attrs: Vec::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Creates a graph with directed edges.
+    #[must_use]
+    pub fn digraph(name: &str) -> Self {
+        Graph::new(name, true)
+    }
+
+    /// Creates a graph with undirected edges.
+    #[must_use]
+    pub fn undirected(name: &str) -> Self {
+        Graph::new(name, false)
+    }
+
+    /// Sets a graph attribute such as `rankdir`.
+    pub fn attr(&mut self, key: &str, value: impl Display) -> &mut Self {
+        self.attrs.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Declares a node. Nodes that are only referenced by edges don't need to be declared.
+    pub fn node(&mut self, id: impl Display) -> &mut Element {
+        self.nodes.push(Element {
+            ids: (id.to_string(), None),
+            attrs: Vec::new(),
+        });
+        self.nodes.last_mut().unwrap()
+    }
+
+    pub fn edge(&mut self, from: impl Display, to: impl Display) -> &mut Element {
+        self.edges.push(Element {
+            ids: (from.to_string(), Some(to.to_string())),
+            attrs: Vec::new(),
+        });
+        self.edges.last_mut().unwrap()
+    }
+
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let (keyword, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        let mut out = format!("{keyword} {} {{\n", quote(&self.name));
+        for (key, value) in &self.attrs {
+            writeln!(out, "    {key}={};", quote(value)).unwrap();
+        }
+
+        for element in self.nodes.iter().chain(&self.edges) {
+            out.push_str("    ");
+            out.push_str(&quote(&element.ids.0));
+            if let Some(to) = &element.ids.1 {
+                write!(out, " {arrow} {}", quote(to)).unwrap();
+            }
+            if !element.attrs.is_empty() {
+                let attrs: Vec<String> = element
+                    .attrs
+                    .iter()
+                    .map(|(key, value)| format!("{key}={}", quote(value)))
+                    .collect();
+                write!(out, " [{}]", attrs.join(", ")).unwrap();
+            }
+            out.push_str(";\n");
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Quotes an identifier, escaping quotes and backslashes. Line breaks become `\n`.
+fn quote(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+/// Parses `--dot <path>` from command-line arguments.
+/// Returns [`None`] if no export was requested.
+pub fn path_from_args(args: &[String]) -> Result<Option<PathBuf>, String> {
+    let Some(index) = args.iter().position(|x| x == "--dot") else {
+        return Ok(None);
+    };
+
+    match args.get(index + 1) {
+        Some(path) if !path.starts_with("--") => Ok(Some(path.into())),
+        _ => Err("Unexpected command-line input. Format: --dot <path>".into()),
+    }
+}
+
+static PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+fn path() -> Option<&'static PathBuf> {
+    PATH.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        path_from_args(&args).unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            None
+        })
+    })
+    .as_ref()
+}
+
+fn write(path: &PathBuf, graph: &Graph) -> Result<(), io::Error> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+
+    let append = STARTED.swap(true, Ordering::SeqCst);
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)?;
+    file.write_all(graph.to_dot().as_bytes())
+}
+
+/// Whether `--dot <path>` was passed to the binary.
+pub fn enabled() -> bool {
+    path().is_some()
+}
+
+/// Writes the graph returned by `build` if DOT export is enabled. The graph is not written again
+/// while the part is benched.
+pub fn export(build: impl FnOnce() -> Graph) {
+    let Some(path) = path().filter(|_| !runner::is_benching()) else {
+        return;
+    };
+
+    let graph = build();
+    match write(path, &graph) {
+        Ok(()) => eprintln!("Wrote graph \"{}\" to {}", graph.name, path.display()),
+        Err(e) => eprintln!("Failed to write DOT graph: {e}"),
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{path_from_args, Graph};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(Into::into).collect()
+    }
+
+    #[test]
+    fn parses_args() {
+        assert_eq!(path_from_args(&args("08 --time")), Ok(None));
+        assert_eq!(
+            path_from_args(&args("08 --dot out.dot --time")),
+            Ok(Some("out.dot".into()))
+        );
+        assert!(path_from_args(&args("08 --dot")).is_err());
+        assert!(path_from_args(&args("08 --dot --time")).is_err());
+    }
+
+    #[test]
+    fn renders_digraph() {
+        let mut graph = Graph::digraph("day 8");
+        graph.attr("rankdir", "LR");
+        graph
+            .node("AAA")
+            .attr("shape", "box")
+            .attr("color", "green");
+        graph.edge("AAA", "BBB").attr("label", 'L');
+        graph.edge("BBB", "AAA");
+
+        let expected = [
+            "digraph \"day 8\" {",
+            "    rankdir=\"LR\";",
+            "    \"AAA\" [shape=\"box\", color=\"green\"];",
+            "    \"AAA\" -> \"BBB\" [label=\"L\"];",
+            "    \"BBB\" -> \"AAA\";",
+            "}",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(graph.to_dot(), expected);
+    }
+
+    #[test]
+    fn renders_undirected_graph_with_escaping() {
+        let mut graph = Graph::undirected("g");
+        graph.edge("a\"b", "c\\d").attr("label", "5\nwide");
+        assert_eq!(
+            graph.to_dot(),
+            "graph \"g\" {\n    \"a\\\"b\" -- \"c\\\\d\" [label=\"5\\nwide\"];\n}\n"
+        );
+    }
+}
@@ -0,0 +1,812 @@
+//! Generators for the individual days, see [`super::generate`].
+use std::collections::{HashMap, HashSet};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use super::{boundary_loop, grid, name_len, stretched, tree_polyomino, unique_names, GenError};
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Lines of letters, digits and spelled out digits with at least one digit each.
+pub fn trebuchet(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut line = String::new();
+            let digit_at = rng.gen_range(0..6);
+            for i in 0..6 {
+                match rng.gen_range(0..3) {
+                    _ if i == digit_at => line.push(char::from(rng.gen_range(b'1'..=b'9'))),
+                    0 => line.push_str(DIGIT_WORDS.choose(rng).unwrap()),
+                    _ => line.push(char::from(rng.gen_range(b'a'..=b'z'))),
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn cube_games(rng: &mut StdRng, size: usize) -> String {
+    (1..=size)
+        .map(|id| {
+            let draws: Vec<String> = (0..rng.gen_range(1..=6))
+                .map(|_| {
+                    let mut colors = vec!["red", "green", "blue"];
+                    colors.shuffle(rng);
+                    colors.truncate(rng.gen_range(1..=3));
+                    colors
+                        .iter()
+                        .map(|color| format!("{} {color}", rng.gen_range(1..=20)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect();
+            format!("Game {id}: {}", draws.join("; "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Numbers and symbols on a grid of dots, numbers don't touch each other horizontally.
+pub fn engine_schematic(rng: &mut StdRng, size: usize) -> String {
+    let mut cells = vec![vec!['.'; size]; size];
+    for line in &mut cells {
+        let mut col = rng.gen_range(0..4);
+        while col < size {
+            if rng.gen_bool(0.15) {
+                line[col] = *b"*#+$/=@%&-".map(char::from).choose(rng).unwrap();
+                col += 2;
+            } else {
+                let len = rng.gen_range(1..=3).min(size - col);
+                for cell in &mut line[col..col + len] {
+                    *cell = char::from(rng.gen_range(b'0'..=b'9'));
+                }
+                col += len + rng.gen_range(1..5);
+            }
+        }
+    }
+    grid(size, size, |row, col| cells[row][col])
+}
+
+pub fn scratchcards(rng: &mut StdRng, size: usize) -> String {
+    let numbers: Vec<u32> = (1..100).collect();
+    let width = size.to_string().len();
+    (1..=size)
+        .map(|id| {
+            let format = |numbers: Vec<&u32>| -> String {
+                numbers
+                    .iter()
+                    .map(|n| format!("{n:>2}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            let winning = format(numbers.choose_multiple(rng, 10).collect());
+            let have = format(numbers.choose_multiple(rng, 25).collect());
+            format!("Card {id:>width$}: {winning} | {have}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Seed ranges and seven maps with disjoint source ranges.
+pub fn almanac(rng: &mut StdRng, size: usize) -> String {
+    const LIMIT: usize = 1 << 32;
+    const CATEGORIES: [&str; 8] = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+
+    let seeds: Vec<String> = (0..size.clamp(1, 10))
+        .map(|_| {
+            let start = rng.gen_range(0..LIMIT / 2);
+            format!("{start} {}", rng.gen_range(1..LIMIT / 16))
+        })
+        .collect();
+    let mut sections = vec![format!("seeds: {}", seeds.join(" "))];
+
+    for pair in CATEGORIES.windows(2) {
+        let mut cuts: Vec<usize> = (0..2 * size.clamp(1, 25))
+            .map(|_| rng.gen_range(0..LIMIT))
+            .collect();
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        let mut lines = vec![format!("{}-to-{} map:", pair[0], pair[1])];
+        for range in cuts.chunks_exact(2) {
+            let len = range[1] - range[0];
+            let dest = rng.gen_range(0..LIMIT - len);
+            lines.push(format!("{dest} {} {len}", range[0]));
+        }
+        sections.push(lines.join("\n"));
+    }
+    sections.join("\n\n")
+}
+
+/// At most four races, so that the concatenated numbers of part two fit into a `u64`.
+pub fn races(rng: &mut StdRng, size: usize) -> String {
+    let (times, distances): (Vec<u64>, Vec<u64>) = (0..size.clamp(1, 4))
+        .map(|_| {
+            let time = rng.gen_range(10..100);
+            let best = (time / 2) * (time - time / 2);
+            (time, rng.gen_range(best / 2..best))
+        })
+        .unzip();
+    let format = |values: Vec<u64>| -> String {
+        values.iter().map(|v| format!("{v:>7}")).collect::<String>()
+    };
+    format!("Time:    {}\nDistance:{}", format(times), format(distances))
+}
+
+pub fn camel_cards(rng: &mut StdRng, size: usize) -> String {
+    let cards: Vec<char> = "AKQJT98765432".chars().collect();
+    let mut hands: HashSet<String> = HashSet::new();
+    let mut lines = Vec::new();
+    while lines.len() < size {
+        // drawing from a few cards at a time gives all hand types.
+        let distinct = rng.gen_range(2..=5);
+        let pool: Vec<char> = cards.choose_multiple(rng, distinct).copied().collect();
+        let hand: String = (0..5).map(|_| *pool.choose(rng).unwrap()).collect();
+        if hands.insert(hand.clone()) {
+            lines.push(format!("{hand} {}", rng.gen_range(1..=1000)));
+        }
+    }
+    lines.join("\n")
+}
+
+/// A network where each start node `..A` runs into a cycle that reaches its end node `..Z` after a
+/// multiple of the instruction length, like the real inputs. Every other step offers two nodes,
+/// one for each instruction. `AAA` leads to `ZZZ`.
+pub fn network(rng: &mut StdRng, size: usize) -> Result<String, GenError> {
+    const PRIMES: [usize; 4] = [3, 5, 7, 11];
+
+    let pattern_len = size.clamp(1, 100);
+    let pattern: String = (0..pattern_len)
+        .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+        .collect();
+
+    let inner_count: usize = PRIMES.iter().map(|p| 2 * (p * pattern_len - 1)).sum();
+    let mut inner = unique_names(rng, inner_count, 3, |name| {
+        !name.ends_with('a') && !name.ends_with('z')
+    })?
+    .into_iter()
+    .map(|name| name.to_uppercase());
+    let mut ends = unique_names(rng, PRIMES.len() - 1, 2, |name| {
+        name != "aa" && name != "zz"
+    })?
+    .into_iter()
+    .map(|name| name.to_uppercase());
+
+    let mut lines = Vec::new();
+    for (ghost, prime) in PRIMES.iter().enumerate() {
+        let (start, end) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => {
+                let name = ends.next().unwrap();
+                (format!("{name}A"), format!("{name}Z"))
+            }
+        };
+
+        // steps 1 to cycle_len - 1 offer a node for each instruction, step 0 (mod cycle_len) is
+        // the start or the end node.
+        let cycle_len = prime * pattern_len;
+        let steps: Vec<(String, String)> = (1..cycle_len)
+            .map(|_| (inner.next().unwrap(), inner.next().unwrap()))
+            .collect();
+        let next = |step: usize| -> (String, String) {
+            match steps.get(step) {
+                Some(pair) => pair.clone(),
+                None => (end.clone(), end.clone()),
+            }
+        };
+
+        let first = next(0);
+        lines.push(format!("{start} = ({}, {})", first.0, first.1));
+        lines.push(format!("{end} = ({}, {})", first.0, first.1));
+        for (i, (left, right)) in steps.iter().enumerate() {
+            let (next_left, next_right) = next(i + 1);
+            lines.push(format!("{left} = ({next_left}, {next_right})"));
+            lines.push(format!("{right} = ({next_left}, {next_right})"));
+        }
+    }
+    lines.shuffle(rng);
+
+    Ok(format!("{pattern}\n\n{}", lines.join("\n")))
+}
+
+/// Sequences of 21 values of random polynomials of degree up to five.
+pub fn oasis_report(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            // a non-zero leading coefficient keeps sequences from being all zeros.
+            let mut coefficients: Vec<i64> = (0..=rng.gen_range(0..=5))
+                .map(|_| rng.gen_range(-5..=5))
+                .collect();
+            *coefficients.last_mut().unwrap() = *[-2, -1, 1, 2].choose(rng).unwrap();
+            (0..21_i64)
+                .map(|x| {
+                    let value = coefficients.iter().rev().fold(0, |acc, c| acc * x + c);
+                    value.to_string()
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A random simple loop of pipes with `S` somewhere on it, surrounded by unconnected junk pipes.
+/// The loop runs around a polyomino that is mostly two cells thick, so it encloses tiles.
+pub fn pipe_maze(rng: &mut StdRng, size: usize) -> String {
+    let lattice = (size.max(5) - 1) / 4;
+    let cells = tree_polyomino(rng, lattice, lattice);
+    let cells = stretched(rng, &cells);
+    let points = boundary_loop(&cells);
+    // the loop lives on the corners of the polyomino, keep a border of at least one tile around it.
+    let dim = cells.len().max(cells[0].len()) + 3;
+
+    let mut tiles = vec![vec!['.'; dim]; dim];
+    for (i, &(row, col)) in points.iter().enumerate() {
+        let prev = points[(i + points.len() - 1) % points.len()];
+        let next = points[(i + 1) % points.len()];
+        let dirs: Vec<(isize, isize)> = [prev, next]
+            .iter()
+            .map(|p| (p.0 as isize - row as isize, p.1 as isize - col as isize))
+            .collect();
+        let has = |dir: (isize, isize)| dirs.contains(&dir);
+        tiles[row + 1][col + 1] = match (has((-1, 0)), has((1, 0)), has((0, -1)), has((0, 1))) {
+            (true, true, _, _) => '|',
+            (_, _, true, true) => '-',
+            (true, _, _, true) => 'L',
+            (true, _, true, _) => 'J',
+            (_, true, true, _) => '7',
+            _ => 'F',
+        };
+    }
+
+    let start = points[rng.gen_range(0..points.len())];
+    let start = (start.0 + 1, start.1 + 1);
+    tiles[start.0][start.1] = 'S';
+
+    // junk pipes next to S could connect to it, so those tiles are left empty.
+    for (row, line) in tiles.iter_mut().enumerate() {
+        for (col, tile) in line.iter_mut().enumerate() {
+            let near_start = row.abs_diff(start.0) + col.abs_diff(start.1) == 1;
+            if *tile == '.' && !near_start && rng.gen_bool(0.5) {
+                *tile = *['|', '-', 'L', 'J', '7', 'F'].choose(rng).unwrap();
+            }
+        }
+    }
+
+    grid(dim, dim, |row, col| tiles[row][col])
+}
+
+/// Galaxies with some empty rows and columns that expand.
+pub fn galaxies(rng: &mut StdRng, size: usize) -> String {
+    let empty_rows: HashSet<usize> = (0..size).filter(|_| rng.gen_bool(0.1)).collect();
+    let empty_cols: HashSet<usize> = (0..size).filter(|_| rng.gen_bool(0.1)).collect();
+    grid(size, size, |row, col| {
+        let empty = empty_rows.contains(&row) || empty_cols.contains(&col);
+        if !empty && rng.gen_bool(0.05) {
+            '#'
+        } else {
+            '.'
+        }
+    })
+}
+
+/// Spring rows with damaged groups that have at least one arrangement.
+pub fn spring_rows(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let len = rng.gen_range(5..=20);
+            let mut springs: Vec<char> = (0..len)
+                .map(|_| if rng.gen_bool(0.4) { '#' } else { '.' })
+                .collect();
+            springs[rng.gen_range(0..len)] = '#';
+
+            let groups: Vec<String> = springs
+                .split(|c| *c == '.')
+                .filter(|group| !group.is_empty())
+                .map(|group| group.len().to_string())
+                .collect();
+
+            for spring in springs.iter_mut() {
+                if rng.gen_bool(0.4) {
+                    *spring = '?';
+                }
+            }
+            let unknown = rng.gen_range(0..len);
+            springs[unknown] = '?';
+
+            format!(
+                "{} {}",
+                springs.iter().collect::<String>(),
+                groups.join(",")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Number of mismatching cells for a reflection between `line` - 1 and `line` of `rows`.
+fn reflection_errors(rows: &[Vec<bool>], line: usize) -> usize {
+    (0..line.min(rows.len() - line))
+        .map(|k| {
+            rows[line - 1 - k]
+                .iter()
+                .zip(&rows[line + k])
+                .filter(|(a, b)| a != b)
+                .count()
+        })
+        .sum()
+}
+
+fn transpose(rows: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    (0..rows[0].len())
+        .map(|col| rows.iter().map(|row| row[col]).collect())
+        .collect()
+}
+
+/// Number of reflection lines with exactly `errors` mismatches, in both directions.
+fn count_reflections(rows: &[Vec<bool>], errors: usize) -> usize {
+    let cols = transpose(rows);
+    (1..rows.len())
+        .filter(|line| reflection_errors(rows, *line) == errors)
+        .count()
+        + (1..cols.len())
+            .filter(|line| reflection_errors(&cols, *line) == errors)
+            .count()
+}
+
+/// Builds a pattern with a perfect vertical reflection and a horizontal one with a single smudge,
+/// retrying until both are the only reflections of their kind.
+fn mirror_pattern(rng: &mut StdRng) -> Vec<Vec<bool>> {
+    loop {
+        let height = rng.gen_range(5..=17);
+        let width = rng.gen_range(5..=17);
+        // the vertical line is off center, so that some columns have no mirror image.
+        let col_line = rng.gen_range(1..width);
+        if 2 * col_line == width {
+            continue;
+        }
+        let row_line = rng.gen_range(1..height);
+
+        let mut rows: Vec<Vec<bool>> = (0..height)
+            .map(|_| (0..width).map(|_| rng.gen_bool(0.5)).collect())
+            .collect();
+        for row in rows.iter_mut() {
+            for k in 0..col_line.min(width - col_line) {
+                row[col_line + k] = row[col_line - 1 - k];
+            }
+        }
+        for k in 0..row_line.min(height - row_line) {
+            rows[row_line + k] = rows[row_line - 1 - k].clone();
+        }
+
+        // smudge a mirrored row in a column without mirror image.
+        let unpaired: Vec<usize> = if 2 * col_line < width {
+            (2 * col_line..width).collect()
+        } else {
+            (0..width - 2 * (width - col_line)).collect()
+        };
+        let reach = row_line.min(height - row_line);
+        let row = rng.gen_range(row_line - reach..row_line + reach);
+        let col = *unpaired.choose(rng).unwrap();
+        rows[row][col] = !rows[row][col];
+
+        if count_reflections(&rows, 0) == 1 && count_reflections(&rows, 1) == 1 {
+            return rows;
+        }
+    }
+}
+
+pub fn mirror_patterns(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let rows = mirror_pattern(rng);
+            grid(rows.len(), rows[0].len(), |row, col| {
+                if rows[row][col] {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+pub fn platform(rng: &mut StdRng, size: usize) -> String {
+    grid(size, size, |_, _| match rng.gen_range(0..100) {
+        0..=19 => 'O',
+        20..=34 => '#',
+        _ => '.',
+    })
+}
+
+pub fn init_sequence(rng: &mut StdRng, size: usize) -> Result<String, GenError> {
+    let labels: Vec<String> = (0..(size / 4).max(1))
+        .map(|_| {
+            let len = rng.gen_range(2..=6);
+            Ok(unique_names(rng, 1, len, |_| true)?.remove(0))
+        })
+        .collect::<Result<_, GenError>>()?;
+    let steps = (0..size)
+        .map(|_| {
+            let label = labels.choose(rng).unwrap();
+            if rng.gen_bool(0.3) {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", rng.gen_range(1..=9))
+            }
+        })
+        .collect::<Vec<_>>();
+    Ok(steps.join(","))
+}
+
+/// Capped at 127 tiles since day 16 stores positions as `i8`.
+pub fn contraption(rng: &mut StdRng, size: usize) -> String {
+    let size = size.min(127);
+    grid(size, size, |_, _| {
+        if rng.gen_bool(0.15) {
+            *['/', '\\', '|', '-'].choose(rng).unwrap()
+        } else {
+            '.'
+        }
+    })
+}
+
+pub fn heat_loss_map(rng: &mut StdRng, size: usize) -> String {
+    grid(size.max(2), size.max(2), |_, _| {
+        char::from(rng.gen_range(b'1'..=b'9'))
+    })
+}
+
+/// Returns the positions of a lattice of `count` points spaced by random gaps from `gaps`.
+fn stretch(rng: &mut StdRng, count: usize, gaps: std::ops::RangeInclusive<i64>) -> Vec<i64> {
+    let mut positions = vec![0];
+    for _ in 1..count {
+        positions.push(positions.last().unwrap() + rng.gen_range(gaps.clone()));
+    }
+    positions
+}
+
+/// Both the plan and the colors describe a simple loop of the same shape with different scales.
+/// Parallel parts of the trench are at least two meters apart so that it never touches itself.
+pub fn dig_plan(rng: &mut StdRng, size: usize) -> String {
+    let lattice = (size / 4).max(2);
+    let points = boundary_loop(&tree_polyomino(rng, lattice, lattice));
+    let corners = 2 * lattice;
+
+    let (small_rows, small_cols) = (stretch(rng, corners, 2..=5), stretch(rng, corners, 2..=5));
+    let max_gap = (0xfffff / corners as i64).max(2);
+    let (large_rows, large_cols) = (
+        stretch(rng, corners, 2..=max_gap),
+        stretch(rng, corners, 2..=max_gap),
+    );
+
+    let mut lines = Vec::new();
+    let mut start = 0;
+    while start < points.len() {
+        let dir = |i: usize| {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            (b.0 as isize - a.0 as isize, b.1 as isize - a.1 as isize)
+        };
+        let mut end = start;
+        while end + 1 < points.len() && dir(end + 1) == dir(start) {
+            end += 1;
+        }
+
+        let (from, to) = (points[start], points[(end + 1) % points.len()]);
+        let length = |rows: &[i64], cols: &[i64]| {
+            (rows[to.0] - rows[from.0]).abs() + (cols[to.1] - cols[from.1]).abs()
+        };
+        let (letter, digit) = match dir(start) {
+            (0, 1) => ('R', 0),
+            (1, 0) => ('D', 1),
+            (0, -1) => ('L', 2),
+            _ => ('U', 3),
+        };
+        lines.push(format!(
+            "{letter} {} (#{:05x}{digit})",
+            length(&small_rows, &small_cols),
+            length(&large_rows, &large_cols)
+        ));
+        start = end + 1;
+    }
+    lines.join("\n")
+}
+
+const CATEGORIES: [char; 4] = ['x', 'm', 'a', 's'];
+
+/// Adds the workflow `name` and the workflows below it to `lines`. Parts reaching the workflow
+/// have ratings within the inclusive `ranges`, conditions always split them into two non-empty
+/// ranges.
+fn workflow(
+    rng: &mut StdRng,
+    name: &str,
+    depth: usize,
+    mut ranges: [(u32, u32); 4],
+    names: &mut impl Iterator<Item = String>,
+    lines: &mut Vec<String>,
+) {
+    let mut target =
+        |rng: &mut StdRng, ranges: [(u32, u32); 4], lines: &mut Vec<String>| -> String {
+            if depth > 0 && rng.gen_bool(0.6) {
+                let child = names.next().unwrap();
+                workflow(rng, &child, depth - 1, ranges, names, lines);
+                child
+            } else if rng.gen_bool(0.5) {
+                "A".to_string()
+            } else {
+                "R".to_string()
+            }
+        };
+
+    let mut rules = Vec::new();
+    for _ in 0..rng.gen_range(1..=3) {
+        let splittable: Vec<usize> = (0..4).filter(|i| ranges[*i].0 < ranges[*i].1).collect();
+        let Some(&i) = splittable.choose(rng) else {
+            break;
+        };
+        let (lo, hi) = ranges[i];
+        let mut matched = ranges;
+        let rule = if rng.gen_bool(0.5) {
+            let value = rng.gen_range(lo + 1..=hi);
+            (matched[i], ranges[i]) = ((lo, value - 1), (value, hi));
+            format!("{}<{value}", CATEGORIES[i])
+        } else {
+            let value = rng.gen_range(lo..hi);
+            (matched[i], ranges[i]) = ((value + 1, hi), (lo, value));
+            format!("{}>{value}", CATEGORIES[i])
+        };
+        rules.push(format!("{rule}:{}", target(rng, matched, lines)));
+    }
+    // the last rule is the fallback without a condition.
+    rules.push(target(rng, ranges, lines));
+
+    lines.push(format!("{name}{{{}}}", rules.join(",")));
+}
+
+/// A tree of workflows starting at `in`, followed by `size` parts.
+pub fn workflows(rng: &mut StdRng, size: usize) -> Result<String, GenError> {
+    let depth = (usize::BITS - size.leading_zeros()).clamp(1, 4) as usize;
+    // each workflow has at most four children.
+    let count = (0..=depth as u32).map(|d| 4_usize.pow(d)).sum();
+    let mut names = unique_names(rng, count, 3, |name| name != "in")?.into_iter();
+    let mut lines = Vec::new();
+    workflow(rng, "in", depth, [(1, 4000); 4], &mut names, &mut lines);
+    lines.shuffle(rng);
+
+    let parts: Vec<String> = (0..size)
+        .map(|_| {
+            let ratings: Vec<String> = CATEGORIES
+                .iter()
+                .map(|c| format!("{c}={}", rng.gen_range(1..=4000)))
+                .collect();
+            format!("{{{}}}", ratings.join(","))
+        })
+        .collect();
+
+    Ok(format!("{}\n\n{}", lines.join("\n"), parts.join("\n")))
+}
+
+/// Modules only send pulses to modules that come later in a random order, so pushing the button
+/// always settles. Names have two letters like the real inputs, more if there are too many modules.
+pub fn module_config(rng: &mut StdRng, size: usize) -> Result<String, GenError> {
+    let count = size.max(2);
+    let names = unique_names(rng, count, name_len(count, 2), |name| name != "output")?;
+    let mut lines = Vec::new();
+
+    let first: Vec<&String> = names.iter().take(rng.gen_range(1..=4)).collect();
+    lines.push(format!(
+        "broadcaster -> {}",
+        first
+            .iter()
+            .map(|s| s.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+
+    for (i, name) in names.iter().enumerate() {
+        let later = &names[i + 1..];
+        let targets: Vec<&str> = if later.is_empty() {
+            vec!["output"]
+        } else {
+            let count = rng.gen_range(1..=3).min(later.len());
+            later
+                .choose_multiple(rng, count)
+                .map(String::as_str)
+                .collect()
+        };
+        let kind = if rng.gen_bool(0.7) { '%' } else { '&' };
+        lines.push(format!("{kind}{name} -> {}", targets.join(", ")));
+    }
+    Ok(lines.join("\n"))
+}
+
+/// An odd square garden with `S` in the center and rock-free center lines and borders.
+pub fn garden(rng: &mut StdRng, size: usize) -> String {
+    let dim = size / 2 * 2 + 1;
+    let center = dim / 2;
+    grid(dim, dim, |row, col| {
+        if (row, col) == (center, center) {
+            'S'
+        } else if row == center
+            || col == center
+            || row == 0
+            || col == 0
+            || row == dim - 1
+            || col == dim - 1
+            || !rng.gen_bool(0.15)
+        {
+            '.'
+        } else {
+            '#'
+        }
+    })
+}
+
+/// `size` disjoint bricks on a 10 x 10 area, stacked roughly `size / 10` high.
+pub fn brick_snapshot(rng: &mut StdRng, size: usize) -> String {
+    let mut occupied: HashSet<(usize, usize, usize)> = HashSet::new();
+    let mut lines = Vec::new();
+    let max_z = (size / 10).max(1) * 3;
+
+    while lines.len() < size {
+        let start = (
+            rng.gen_range(0..10),
+            rng.gen_range(0..10),
+            rng.gen_range(1..=max_z),
+        );
+        let len = rng.gen_range(0..4);
+        let end = match rng.gen_range(0..3) {
+            0 => ((start.0 + len).min(9), start.1, start.2),
+            1 => (start.0, (start.1 + len).min(9), start.2),
+            _ => (start.0, start.1, start.2 + len),
+        };
+
+        let cubes: Vec<_> = (start.0..=end.0)
+            .flat_map(|x| {
+                (start.1..=end.1).flat_map(move |y| (start.2..=end.2).map(move |z| (x, y, z)))
+            })
+            .collect();
+        if cubes.iter().any(|cube| occupied.contains(cube)) {
+            continue;
+        }
+        occupied.extend(cubes);
+        lines.push(format!(
+            "{},{},{}~{},{},{}",
+            start.0, start.1, start.2, end.0, end.1, end.2
+        ));
+    }
+    lines.join("\n")
+}
+
+/// A lattice of junctions connected by corridors, with slopes pointing right and down around
+/// every junction like the real inputs. The lattice is at most 6 x 6 like the real inputs, the
+/// longest path search of part two blows up on larger ones.
+pub fn hiking_trails(rng: &mut StdRng, size: usize) -> String {
+    let junctions = (size / 8).clamp(2, 6);
+    let rows: Vec<usize> = stretch(rng, junctions, 3..=8)
+        .iter()
+        .map(|r| *r as usize + 2)
+        .collect();
+    let cols: Vec<usize> = stretch(rng, junctions, 3..=8)
+        .iter()
+        .map(|c| *c as usize + 1)
+        .collect();
+    let (height, width) = (rows[junctions - 1] + 3, cols[junctions - 1] + 2);
+
+    let mut tiles = vec![vec!['#'; width]; height];
+    for &row in &rows {
+        tiles[row][cols[0]..=cols[junctions - 1]].fill('.');
+    }
+    for &col in &cols {
+        for line in &mut tiles[rows[0]..=rows[junctions - 1]] {
+            line[col] = '.';
+        }
+    }
+
+    // entrance above the first junction and exit below the last one.
+    for line in &mut tiles[..rows[0]] {
+        line[cols[0]] = '.';
+    }
+    for line in &mut tiles[rows[junctions - 1]..] {
+        line[cols[junctions - 1]] = '.';
+    }
+
+    for &row in &rows {
+        for &col in &cols {
+            for (r, c, slope) in [
+                (row, col - 1, '>'),
+                (row, col + 1, '>'),
+                (row - 1, col, 'v'),
+                (row + 1, col, 'v'),
+            ] {
+                if tiles[r][c] == '.' {
+                    tiles[r][c] = slope;
+                }
+            }
+        }
+    }
+
+    grid(height, width, |row, col| tiles[row][col])
+}
+
+/// Hailstones that are all hit by a single rock thrown from an integer position.
+pub fn hailstones(rng: &mut StdRng, size: usize) -> String {
+    let rock: Vec<i64> = (0..3)
+        .map(|_| rng.gen_range(100_000_000_000_000..300_000_000_000_000))
+        .collect();
+    let rock_vel: Vec<i64> = (0..3).map(|_| rng.gen_range(-300..=300)).collect();
+
+    (0..size)
+        .map(|_| {
+            let time = rng.gen_range(100_000_000_000..1_000_000_000_000_i64);
+            let vel: Vec<i64> = (0..3).map(|_| rng.gen_range(-300..=300)).collect();
+            // the hailstone meets the rock at `time`.
+            let pos: Vec<String> = (0..3)
+                .map(|i| (rock[i] + (rock_vel[i] - vel[i]) * time).to_string())
+                .collect();
+            let vel: Vec<String> = vel.iter().map(i64::to_string).collect();
+            format!("{} @ {}", pos.join(", "), vel.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Two well connected groups of components joined by exactly three wires. Names have three letters
+/// like the real inputs, more if there are too many components.
+pub fn wiring_diagram(rng: &mut StdRng, size: usize) -> Result<String, GenError> {
+    let count = size.max(10);
+    let names = unique_names(rng, count, name_len(count, 3), |_| true)?;
+    let (left, right) = names.split_at(names.len() / 2);
+
+    let mut edges: Vec<(&String, &String)> = Vec::new();
+    for group in [left, right] {
+        // connecting each component to the next two keeps the group 4-edge-connected.
+        for (i, name) in group.iter().enumerate() {
+            for offset in [1, 2] {
+                edges.push((name, &group[(i + offset) % group.len()]));
+            }
+            if rng.gen_bool(0.5) {
+                edges.push((name, group.choose(rng).unwrap()));
+            }
+        }
+    }
+    let mut cut: HashSet<(&String, &String)> = HashSet::new();
+    while cut.len() < 3 {
+        cut.insert((left.choose(rng).unwrap(), right.choose(rng).unwrap()));
+    }
+    let mut cut: Vec<_> = cut.into_iter().collect();
+    cut.sort();
+    edges.extend(cut);
+
+    let mut unique: HashSet<(&String, &String)> = HashSet::new();
+    let mut connections: HashMap<&String, Vec<&String>> = HashMap::new();
+    for (a, b) in edges {
+        if a != b && !unique.contains(&(b, a)) && unique.insert((a, b)) {
+            connections.entry(a).or_default().push(b);
+        }
+    }
+
+    let mut lines: Vec<String> = connections
+        .iter()
+        .map(|(name, others)| {
+            let others: Vec<&str> = others.iter().map(|s| s.as_str()).collect();
+            format!("{name}: {}", others.join(" "))
+        })
+        .collect();
+    lines.sort();
+    lines.shuffle(rng);
+    Ok(lines.join("\n"))
+}
@@ -0,0 +1,393 @@
+//! Seeded random input generators, used to stress test and benchmark solutions on inputs larger
+//! than the examples. Generated inputs satisfy the assumptions the puzzles make about real inputs.
+//!
+//! The `size` of an input is the number of lines for line-based puzzles and the side length of
+//! the grid for grid puzzles. Days scale it down where the puzzle needs it, e.g. day 6 has at
+//! most four races.
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::Display;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::Day;
+
+mod days;
+
+pub const DEFAULT_SIZE: usize = 100;
+
+/// Generates an input for `day`. The same seed and size always yield the same input.
+/// Fails if the puzzle can't be scaled up to `size`, e.g. if it runs out of names.
+pub fn generate(day: Day, size: usize, seed: u64) -> Result<String, GenError> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let size = size.max(1);
+    let input = match day.into_inner() {
+        1 => days::trebuchet(&mut rng, size),
+        2 => days::cube_games(&mut rng, size),
+        3 => days::engine_schematic(&mut rng, size),
+        4 => days::scratchcards(&mut rng, size),
+        5 => days::almanac(&mut rng, size),
+        6 => days::races(&mut rng, size),
+        7 => days::camel_cards(&mut rng, size),
+        8 => days::network(&mut rng, size)?,
+        9 => days::oasis_report(&mut rng, size),
+        10 => days::pipe_maze(&mut rng, size),
+        11 => days::galaxies(&mut rng, size),
+        12 => days::spring_rows(&mut rng, size),
+        13 => days::mirror_patterns(&mut rng, size),
+        14 => days::platform(&mut rng, size),
+        15 => days::init_sequence(&mut rng, size)?,
+        16 => days::contraption(&mut rng, size),
+        17 => days::heat_loss_map(&mut rng, size),
+        18 => days::dig_plan(&mut rng, size),
+        19 => days::workflows(&mut rng, size)?,
+        20 => days::module_config(&mut rng, size)?,
+        21 => days::garden(&mut rng, size),
+        22 => days::brick_snapshot(&mut rng, size),
+        23 => days::hiking_trails(&mut rng, size),
+        24 => days::hailstones(&mut rng, size),
+        25 => days::wiring_diagram(&mut rng, size)?,
+        _ => unreachable!("days are between 1 and 25"),
+    };
+    Ok(input)
+}
+
+/// Don't pick names by chance once more than this share of the names is needed.
+const MAX_NAME_SHARE: usize = 2;
+
+/// Returns the length of names with at least `min` letters so that `count` names fill at most
+/// half of the possible names.
+fn name_len(count: usize, min: usize) -> usize {
+    (min..)
+        .find(|len| {
+            26_usize
+                .checked_pow(*len as u32)
+                .is_none_or(|space| count * MAX_NAME_SHARE <= space)
+        })
+        .unwrap()
+}
+
+/// Returns `count` distinct names of `len` lowercase letters that are accepted by `allowed`.
+/// Fails if there are not enough such names.
+fn unique_names(
+    rng: &mut StdRng,
+    count: usize,
+    len: usize,
+    allowed: impl Fn(&str) -> bool,
+) -> Result<Vec<String>, GenError> {
+    let space = 26_usize.checked_pow(len as u32).unwrap_or(usize::MAX);
+    if count * MAX_NAME_SHARE <= space {
+        // random names rarely collide while most names are free. Only if `allowed` rejects too
+        // many of them, all names are listed below.
+        let mut names: HashSet<String> = HashSet::new();
+        let mut ordered = Vec::with_capacity(count);
+        for _ in 0..64 * count {
+            if ordered.len() == count {
+                break;
+            }
+            let name: String = (0..len)
+                .map(|_| char::from(rng.gen_range(b'a'..=b'z')))
+                .collect();
+            if allowed(&name) && names.insert(name.clone()) {
+                ordered.push(name);
+            }
+        }
+        if ordered.len() == count {
+            return Ok(ordered);
+        }
+    }
+
+    if space > 1 << 20 {
+        return Err(GenError(format!(
+            "too few names of {len} letters are allowed to pick {count}"
+        )));
+    }
+    let mut names: Vec<String> = (0..space)
+        .map(|mut index| {
+            let mut name = vec![b'a'; len];
+            for letter in name.iter_mut().rev() {
+                *letter += (index % 26) as u8;
+                index /= 26;
+            }
+            String::from_utf8(name).unwrap()
+        })
+        .filter(|name| allowed(name))
+        .collect();
+    if names.len() < count {
+        return Err(GenError(format!(
+            "there are only {} names of {len} letters, {count} are needed",
+            names.len()
+        )));
+    }
+    names.shuffle(rng);
+    names.truncate(count);
+    Ok(names)
+}
+
+/// Draws a grid of `rows` x `cols` where each cell is picked by `cell`.
+fn grid(rows: usize, cols: usize, mut cell: impl FnMut(usize, usize) -> char) -> String {
+    (0..rows)
+        .map(|row| (0..cols).map(|col| cell(row, col)).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Builds a tree-shaped polyomino on a grid of `(2 * rows - 1) x (2 * cols - 1)` cells from a
+/// random spanning tree of a `rows` x `cols` lattice. Cells at even coordinates are tree nodes,
+/// the cells between them are tree edges. Such a polyomino has no holes and no cells that only
+/// touch at a corner, so its boundary is a simple closed curve.
+fn tree_polyomino(rng: &mut StdRng, rows: usize, cols: usize) -> Vec<Vec<bool>> {
+    let mut cells = vec![vec![false; 2 * cols - 1]; 2 * rows - 1];
+    let mut visited = vec![vec![false; cols]; rows];
+    let start = (rng.gen_range(0..rows), rng.gen_range(0..cols));
+    let mut frontier = vec![start];
+    visited[start.0][start.1] = true;
+    cells[2 * start.0][2 * start.1] = true;
+
+    // randomized Prim's: grow the tree from a random node of the frontier.
+    while !frontier.is_empty() {
+        let index = rng.gen_range(0..frontier.len());
+        let (row, col) = frontier[index];
+        let neighbors: Vec<(usize, usize)> = [(0, 1), (1, 0), (0, -1), (-1, 0)]
+            .iter()
+            .filter_map(|(dr, dc)| {
+                let next = (row.checked_add_signed(*dr)?, col.checked_add_signed(*dc)?);
+                (next.0 < rows && next.1 < cols && !visited[next.0][next.1]).then_some(next)
+            })
+            .collect();
+
+        let Some(&next) = neighbors.choose(rng) else {
+            frontier.swap_remove(index);
+            continue;
+        };
+
+        visited[next.0][next.1] = true;
+        cells[2 * next.0][2 * next.1] = true;
+        cells[row + next.0][col + next.1] = true;
+        frontier.push(next);
+    }
+
+    cells
+}
+
+/// Stretches the rows and columns of a [`tree_polyomino`] to one or two cells each, those of tree
+/// nodes always to two. Stretching keeps the boundary simple, and every node now has a corner
+/// inside of it.
+fn stretched(rng: &mut StdRng, cells: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let mut widths = |len: usize| -> Vec<usize> {
+        (0..len)
+            .map(|i| if i % 2 == 0 { 2 } else { rng.gen_range(1..=2) })
+            .collect()
+    };
+    let heights = widths(cells.len());
+    let widths = widths(cells[0].len());
+    cells
+        .iter()
+        .zip(heights)
+        .flat_map(|(row, height)| {
+            let row: Vec<bool> = row
+                .iter()
+                .zip(&widths)
+                .flat_map(|(cell, width)| vec![*cell; *width])
+                .collect();
+            vec![row; height]
+        })
+        .collect()
+}
+
+/// Traces the boundary of a polyomino without holes and returns its corner points in order,
+/// starting at the top-left corner. Points are given as `(row, col)` in a grid of
+/// `(rows + 1) x (cols + 1)` corners.
+fn boundary_loop(cells: &[Vec<bool>]) -> Vec<(usize, usize)> {
+    let occupied = |row: isize, col: isize| -> bool {
+        row >= 0
+            && col >= 0
+            && cells
+                .get(row as usize)
+                .and_then(|line| line.get(col as usize))
+                .copied()
+                .unwrap_or(false)
+    };
+
+    let mut adjacent: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+    let mut connect = |a: (usize, usize), b: (usize, usize)| {
+        adjacent.entry(a).or_default().push(b);
+        adjacent.entry(b).or_default().push(a);
+    };
+
+    for (row, line) in cells.iter().enumerate() {
+        for (col, _) in line.iter().enumerate().filter(|(_, cell)| **cell) {
+            let (r, c) = (row as isize, col as isize);
+            if !occupied(r - 1, c) {
+                connect((row, col), (row, col + 1));
+            }
+            if !occupied(r + 1, c) {
+                connect((row + 1, col), (row + 1, col + 1));
+            }
+            if !occupied(r, c - 1) {
+                connect((row, col), (row + 1, col));
+            }
+            if !occupied(r, c + 1) {
+                connect((row, col + 1), (row + 1, col + 1));
+            }
+        }
+    }
+
+    let start = *adjacent.keys().min().unwrap();
+    let mut points = vec![start];
+    let mut prev = start;
+    let mut cur = adjacent[&start][0];
+    while cur != start {
+        points.push(cur);
+        let next = adjacent[&cur]
+            .iter()
+            .find(|p| **p != prev)
+            .copied()
+            .unwrap();
+        prev = cur;
+        cur = next;
+    }
+    points
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenError(String);
+
+impl Error for GenError {}
+
+impl Display for GenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{
+        boundary_loop, generate, name_len, stretched, tree_polyomino, unique_names, GenError,
+        DEFAULT_SIZE,
+    };
+    use crate::{all_days, day, validate, Day};
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashSet;
+
+    #[test]
+    fn is_deterministic() {
+        for day in all_days() {
+            assert_eq!(generate(day, 20, 7), generate(day, 20, 7), "day {day}");
+        }
+        assert_ne!(generate(day!(7), 20, 1), generate(day!(7), 20, 2));
+    }
+
+    #[test]
+    fn generates_all_days() {
+        for day in all_days() {
+            for size in [1, 5, DEFAULT_SIZE] {
+                assert!(!generate(day, size, 3).unwrap().is_empty(), "day {day}");
+            }
+        }
+    }
+
+    #[test]
+    fn generates_rectangular_grids() {
+        for day in [3, 10, 11, 14, 16, 17, 21, 23] {
+            let input = generate(Day::new(day).unwrap(), 30, 11).unwrap();
+            assert!(validate::rectangular_grid(&input).is_ok(), "day {day}");
+        }
+    }
+
+    #[test]
+    fn traces_simple_boundary() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let cells = tree_polyomino(&mut rng, 6, 8);
+        let points = boundary_loop(&cells);
+        let unique: HashSet<_> = points.iter().collect();
+        assert_eq!(unique.len(), points.len());
+        // consecutive points are neighbors, including the wrap-around.
+        for (i, a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            assert_eq!(a.0.abs_diff(b.0) + a.1.abs_diff(b.1), 1);
+        }
+    }
+
+    #[test]
+    fn runs_out_of_names() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(unique_names(&mut rng, 676, 2, |_| true).unwrap().len(), 676);
+        assert_eq!(
+            unique_names(&mut rng, 677, 2, |_| true),
+            Err(GenError(
+                "there are only 676 names of 2 letters, 677 are needed".into()
+            ))
+        );
+        // rejection sampling gives up and lists the few allowed names instead.
+        let names = unique_names(&mut rng, 2, 3, |name| name.starts_with("zz")).unwrap();
+        assert!(names.iter().all(|name| name.starts_with("zz")));
+
+        assert_eq!(name_len(338, 2), 2);
+        assert_eq!(name_len(339, 2), 3);
+        let input = generate(day!(20), 700, 1).unwrap();
+        assert_eq!(input.lines().count(), 701);
+        assert!(generate(day!(25), 20_000, 1).is_ok());
+    }
+
+    #[test]
+    fn encloses_pipe_tiles() {
+        for (size, seed) in [(5, 1), (30, 5), (80, 1), (80, 2), (80, 3)] {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let lattice = (size - 1) / 4;
+            let cells = tree_polyomino(&mut rng, lattice, lattice);
+            let cells = stretched(&mut rng, &cells);
+            let points = boundary_loop(&cells);
+            // shoelace formula and Pick's theorem give the number of points inside the loop.
+            let twice_area = points
+                .iter()
+                .zip(points.iter().cycle().skip(1))
+                .map(|(a, b)| a.0 as i64 * b.1 as i64 - b.0 as i64 * a.1 as i64)
+                .sum::<i64>()
+                .abs();
+            let inside = (twice_area - points.len() as i64) / 2 + 1;
+            assert!(inside > 0, "size {size}, seed {seed}");
+        }
+    }
+
+    #[test]
+    fn generates_pipe_loop() {
+        let input = generate(day!(10), 20, 9).unwrap();
+        let s = validate::find_unique(&input, 'S').unwrap();
+        let grid: Vec<Vec<char>> = input.lines().map(|l| l.chars().collect()).collect();
+        let connects = [
+            (s.0.wrapping_sub(1), s.1, "|7F"),
+            (s.0 + 1, s.1, "|LJ"),
+            (s.0, s.1.wrapping_sub(1), "-LF"),
+            (s.0, s.1 + 1, "-J7"),
+        ]
+        .iter()
+        .filter(|(r, c, pipes)| {
+            grid.get(*r)
+                .and_then(|line| line.get(*c))
+                .is_some_and(|ch| pipes.contains(*ch))
+        })
+        .count();
+        assert_eq!(connects, 2);
+    }
+
+    #[test]
+    fn generates_disjoint_bricks() {
+        let input = generate(day!(22), 200, 4).unwrap();
+        let mut occupied = HashSet::new();
+        for line in input.lines() {
+            let (start, end) = line.split_once('~').unwrap();
+            let parse =
+                |s: &str| -> Vec<i64> { s.split(',').map(|n| n.parse().unwrap()).collect() };
+            let (a, b) = (parse(start), parse(end));
+            for x in a[0]..=b[0] {
+                for y in a[1]..=b[1] {
+                    for z in a[2]..=b[2] {
+                        assert!(occupied.insert((x, y, z)));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,314 @@
+//! The Holiday ASCII String Helper Manual Arrangement Procedure (HASHMAP) of day 15: a hash map
+//! with 256 boxes that keeps the entries of every box in insertion order.
+//!
+//! Replacing the value of a key keeps its slot, removing a key closes the gap behind it. The
+//! position of a lens in its box is part of the puzzle answer, so unlike
+//! [`std::collections::HashMap`] the iteration order is fully determined by the operations.
+use std::borrow::Borrow;
+use std::fmt::Debug;
+
+use crate::Answer;
+
+/// Number of boxes, one for every value of a [`Hasher`].
+pub const BOXES: usize = 256;
+
+/// Hash function that picks the box of a key.
+pub type Hasher = fn(&[u8]) -> u8;
+
+/// The HASH algorithm of day 15: for every byte, add it, multiply by 17 and keep the remainder of
+/// dividing by 256.
+#[must_use]
+pub fn hash(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0_u8, |acc, byte| acc.wrapping_add(*byte).wrapping_mul(17))
+}
+
+/// Hash map with [`BOXES`] boxes of entries kept in insertion order.
+#[derive(Clone)]
+pub struct HolidayHashMap<K, V> {
+    boxes: Vec<Vec<(K, V)>>,
+    hasher: Hasher,
+    len: usize,
+}
+
+impl<K, V> HolidayHashMap<K, V> {
+    /// An empty map using the day 15 [`hash`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_hasher(hash)
+    }
+
+    /// An empty map that puts every key into the box picked by `hasher`.
+    #[must_use]
+    pub fn with_hasher(hasher: Hasher) -> Self {
+        Self {
+            boxes: (0..BOXES).map(|_| Vec::new()).collect(),
+            hasher,
+            len: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Entries of box `index` in insertion order. Panics if `index` is not below [`BOXES`].
+    #[must_use]
+    pub fn lens_box(&self, index: usize) -> &[(K, V)] {
+        &self.boxes[index]
+    }
+
+    /// All entries, box by box and in insertion order within a box.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.boxes.iter().flatten().map(|(key, value)| (key, value))
+    }
+
+    /// Removes all entries, keeping the hash function.
+    pub fn clear(&mut self) {
+        self.boxes.iter_mut().for_each(Vec::clear);
+        self.len = 0;
+    }
+
+    /// Sum of one-based box number times one-based slot times value of every entry.
+    #[must_use]
+    pub fn focusing_power(&self) -> Answer
+    where
+        V: Copy + Into<Answer>,
+    {
+        self.boxes
+            .iter()
+            .enumerate()
+            .flat_map(|(box_index, entries)| {
+                entries.iter().enumerate().map(move |(slot, (_, value))| {
+                    Answer::from(box_index + 1) * Answer::from(slot + 1) * (*value).into()
+                })
+            })
+            .sum()
+    }
+}
+
+impl<K: Eq + AsRef<[u8]>, V> HolidayHashMap<K, V> {
+    fn find<Q>(&self, key: &Q) -> (usize, Option<usize>)
+    where
+        K: Borrow<Q>,
+        Q: Eq + AsRef<[u8]> + ?Sized,
+    {
+        let index = usize::from((self.hasher)(key.as_ref()));
+        let slot = self.boxes[index]
+            .iter()
+            .position(|(k, _)| k.borrow() == key);
+        (index, slot)
+    }
+
+    /// Inserts `value` for `key` and returns the previous value. An existing key keeps its slot,
+    /// a new key goes behind the other entries of its box.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.find(&key) {
+            (index, Some(slot)) => Some(std::mem::replace(&mut self.boxes[index][slot].1, value)),
+            (index, None) => {
+                self.boxes[index].push((key, value));
+                self.len += 1;
+                None
+            }
+        }
+    }
+
+    /// Removes `key` and returns its value, moving the entries behind it forward.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + AsRef<[u8]> + ?Sized,
+    {
+        let (index, slot) = self.find(key);
+        let (_, value) = self.boxes[index].remove(slot?);
+        self.len -= 1;
+        Some(value)
+    }
+
+    #[must_use]
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + AsRef<[u8]> + ?Sized,
+    {
+        let (index, slot) = self.find(key);
+        slot.map(|slot| &self.boxes[index][slot].1)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + AsRef<[u8]> + ?Sized,
+    {
+        let (index, slot) = self.find(key);
+        slot.map(|slot| &mut self.boxes[index][slot].1)
+    }
+
+    #[must_use]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + AsRef<[u8]> + ?Sized,
+    {
+        self.find(key).1.is_some()
+    }
+}
+
+impl<K, V> Default for HolidayHashMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for HolidayHashMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Eq + AsRef<[u8]>, V> Extend<(K, V)> for HolidayHashMap<K, V> {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Eq + AsRef<[u8]>, V> FromIterator<(K, V)> for HolidayHashMap<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{hash, HolidayHashMap};
+    use crate::Answer;
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn hashes_labels() {
+        assert_eq!(hash(b"HASH"), 52);
+        assert_eq!(hash(b"rn"), 0);
+        assert_eq!(hash(b"qp"), 1);
+        assert_eq!(hash(b"pc"), 3);
+        assert_eq!(hash(b""), 0);
+    }
+
+    #[test]
+    fn arranges_example_lenses() {
+        let mut map = HolidayHashMap::new();
+        map.insert("rn", 1_u8);
+        map.remove("cm");
+        map.insert("qp", 3);
+        map.insert("cm", 2);
+        map.remove("qp");
+        map.insert("pc", 4);
+        map.insert("ot", 9);
+        map.insert("ab", 5);
+        map.remove("pc");
+        map.insert("pc", 6);
+        assert_eq!(map.insert("ot", 7), Some(9));
+
+        assert_eq!(map.len(), 5);
+        assert_eq!(map.lens_box(0), [("rn", 1), ("cm", 2)]);
+        assert_eq!(map.lens_box(3), [("ot", 7), ("ab", 5), ("pc", 6)]);
+        assert_eq!(map.get("ot"), Some(&7));
+        assert!(!map.contains_key("qp"));
+        assert_eq!(map.focusing_power(), Answer::from(145));
+
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(map.focusing_power(), Answer::from(0));
+    }
+
+    #[test]
+    fn borrows_owned_keys() {
+        let mut map: HolidayHashMap<String, u32> = [("a".to_string(), 1), ("b".to_string(), 2)]
+            .into_iter()
+            .collect();
+        *map.get_mut("a").unwrap() += 10;
+        assert_eq!(map.remove("b"), Some(2));
+        assert_eq!(map.remove("b"), None);
+        assert_eq!(format!("{map:?}"), r#"{"a": 11}"#);
+    }
+
+    #[test]
+    fn orders_single_box_by_insertion() {
+        // a single box keeps everything in insertion order.
+        let mut map = HolidayHashMap::with_hasher(|_| 0);
+        map.extend([("c", 1_u8), ("a", 2), ("b", 3)]);
+        map.insert("a", 4);
+        assert_eq!(map.lens_box(0), [("c", 1), ("a", 4), ("b", 3)]);
+        assert_eq!(map.focusing_power(), Answer::from(1 + 2 * 4 + 3 * 3));
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Insert(String, u8),
+        Remove(String),
+    }
+
+    fn ops() -> impl Strategy<Value = Vec<Op>> {
+        let key = "[a-d]{1,3}";
+        let op = prop_oneof![
+            (key, any::<u8>()).prop_map(|(key, value)| Op::Insert(key, value)),
+            key.prop_map(Op::Remove),
+        ];
+        prop::collection::vec(op, 0..60)
+    }
+
+    proptest! {
+        #[test]
+        fn matches_std_hash_map(ops in ops(), small in any::<bool>()) {
+            // a hasher with only a few boxes exercises long boxes.
+            let mut map = if small {
+                HolidayHashMap::with_hasher(|bytes| hash(bytes) % 3)
+            } else {
+                HolidayHashMap::new()
+            };
+            let mut expected = HashMap::new();
+            // entries in insertion order, keeping the slot on replacement.
+            let mut order: Vec<(String, u8)> = Vec::new();
+
+            for op in ops {
+                match op {
+                    Op::Insert(key, value) => {
+                        prop_assert_eq!(map.insert(key.clone(), value), expected.insert(key.clone(), value));
+                        match order.iter_mut().find(|(k, _)| *k == key) {
+                            Some(entry) => entry.1 = value,
+                            None => order.push((key, value)),
+                        }
+                    }
+                    Op::Remove(key) => {
+                        prop_assert_eq!(map.remove(key.as_str()), expected.remove(&key));
+                        order.retain(|(k, _)| *k != key);
+                    }
+                }
+                prop_assert_eq!(map.len(), expected.len());
+            }
+
+            for (key, value) in &expected {
+                prop_assert_eq!(map.get(key.as_str()), Some(value));
+            }
+            prop_assert!(!map.contains_key("e"));
+
+            // the stable sort keeps insertion order within a box.
+            order.sort_by_key(|(key, _)| (map.hasher)(key.as_bytes()));
+            let entries: Vec<(String, u8)> = map.iter().map(|(k, v)| (k.clone(), *v)).collect();
+            prop_assert_eq!(entries, order);
+        }
+    }
+}
@@ -1,4 +1,22 @@
+mod answer;
+pub mod bricks;
+pub mod calibration;
+pub mod cards;
+pub mod corridors;
+pub mod crucible;
 mod day;
+pub mod debugger;
+pub mod differential;
+pub mod dot;
+pub mod gen;
+pub mod holiday;
+pub mod math;
+pub mod network;
+pub mod poly;
 pub mod template;
+pub mod validate;
+pub mod viz;
+pub mod workflow;
 
+pub use answer::*;
 pub use day::*;
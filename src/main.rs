@@ -1,10 +1,15 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, check_input, compare, download, gen, progress, read, scaffold, solve,
+};
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::{path::PathBuf, process};
 
-    use advent_of_code::Day;
+    use advent_of_code::{
+        template::{report::ReportFormat, InputSource},
+        Day,
+    };
 
     pub enum AppArguments {
         Download {
@@ -19,23 +24,137 @@ mod args {
         Solve {
             day: Day,
             release: bool,
+            checked: bool,
             time: bool,
             submit: Option<u8>,
+            input: InputSource,
+            /// Options for visualization, graph export and stepping that are passed on as is.
+            debug_args: Vec<String>,
+        },
+        CheckInput {
+            day: Option<Day>,
+            input: InputSource,
+        },
+        Compare {
+            day: Day,
+            part: Option<u8>,
+        },
+        Gen {
+            day: Day,
+            size: Option<usize>,
+            seed: Option<u64>,
+            out: Option<PathBuf>,
         },
         All {
             release: bool,
+            checked: bool,
             time: bool,
+            report: Option<(ReportFormat, String)>,
         },
+        Progress,
+    }
+
+    fn parse_input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<InputSource, Box<dyn std::error::Error>> {
+        let input = if let Some(path) = args.opt_value_from_str::<_, String>("--input")? {
+            if path == "-" {
+                InputSource::Stdin
+            } else {
+                InputSource::Path(path.into())
+            }
+        } else {
+            // the example number is optional, e.g. `--example` or `--example 2`, so an option
+            // following the flag is left in place.
+            match args.opt_value_from_str("--example") {
+                Ok(example) => {
+                    example.map_or(InputSource::Inputs, |n| InputSource::Example(Some(n)))
+                }
+                Err(pico_args::Error::Utf8ArgumentParsingFailed { value, .. })
+                    if !value.starts_with('-') =>
+                {
+                    return Err(format!("invalid example number: {value}").into())
+                }
+                Err(_) if args.contains("--example") => InputSource::Example(None),
+                Err(e) => return Err(e.into()),
+            }
+        };
+        Ok(input)
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    /// Collects `--visualize [ansi|ppm|png]` and `--frames <dir>` to be passed on to the solution binary.
+    fn parse_visualize(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let frames: Option<String> = args.opt_value_from_str("--frames")?;
+
+        let mut visualize = match args.opt_value_from_str::<_, String>("--visualize") {
+            Ok(Some(output)) if ["ansi", "ppm", "png"].contains(&output.as_str()) => {
+                vec!["--visualize".to_string(), output]
+            }
+            Ok(Some(output)) => {
+                return Err(format!("unknown visualization output: {output}").into())
+            }
+            Ok(None) => vec![],
+            // `--visualize` without an output defaults to the terminal.
+            Err(_) if args.contains("--visualize") => vec!["--visualize".to_string()],
+            Err(e) => return Err(e.into()),
+        };
+
+        if let Some(frames) = frames {
+            visualize.push("--frames".into());
+            visualize.push(frames);
+        }
 
+        Ok(visualize)
+    }
+
+    pub fn parse(
+        mut args: pico_args::Arguments,
+    ) -> Result<AppArguments, Box<dyn std::error::Error>> {
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+            Some("all") => {
+                let release = args.contains("--release");
+                let checked = args.contains("--checked");
+                let time = args.contains("--time");
+                let format: Option<ReportFormat> = args.opt_value_from_str("--report")?;
+                let out: Option<String> = args.opt_value_from_str("--out")?;
+
+                let report = match (format, out) {
+                    (Some(format), out) if time => Some((
+                        format,
+                        out.unwrap_or_else(|| format!("benchmarks.{}", format.extension())),
+                    )),
+                    (None, None) => None,
+                    _ => {
+                        eprintln!("`--report <format>` requires `--time`, `--out <path>` requires `--report <format>`.");
+                        process::exit(1);
+                    }
+                };
+
+                AppArguments::All {
+                    release,
+                    checked,
+                    time,
+                    report,
+                }
+            }
+            Some("check-input") => {
+                let day = args.opt_free_from_str()?;
+                let input = parse_input_source(&mut args)?;
+                AppArguments::CheckInput { day, input }
+            }
+            Some("compare") => AppArguments::Compare {
+                day: args.free_from_str()?,
+                part: args.opt_value_from_str("--part")?,
+            },
+            Some("gen") => AppArguments::Gen {
+                day: args.free_from_str()?,
+                size: args.opt_value_from_str("--size")?,
+                seed: args.opt_value_from_str("--seed")?,
+                out: args.opt_value_from_str("--out")?,
             },
+            Some("progress") => AppArguments::Progress,
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -45,12 +164,34 @@ mod args {
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
-            },
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                // the input source goes first, so options with an optional value like
+                // `--visualize` can't take `--example` as their value.
+                let input = parse_input_source(&mut args)?;
+                let release = args.contains("--release");
+                let checked = args.contains("--checked");
+                let submit = args.opt_value_from_str("--submit")?;
+                let time = args.contains("--time");
+                let mut debug_args = parse_visualize(&mut args)?;
+                if let Some(dot) = args.opt_value_from_str::<_, String>("--dot")? {
+                    debug_args.push("--dot".into());
+                    debug_args.push(dot);
+                }
+                if args.contains("--step") {
+                    debug_args.push("--step".into());
+                }
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    checked,
+                    time,
+                    submit,
+                    input,
+                    debug_args,
+                }
+            }
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -68,25 +209,89 @@ mod args {
 
         Ok(app_args)
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{parse, AppArguments};
+        use advent_of_code::template::InputSource;
+
+        fn solve(s: &str) -> (InputSource, Vec<String>) {
+            let args = s.split_whitespace().map(Into::into).collect();
+            match parse(pico_args::Arguments::from_vec(args)) {
+                Ok(AppArguments::Solve {
+                    input, debug_args, ..
+                }) => (input, debug_args),
+                _ => panic!("`{s}` is not a valid solve command"),
+            }
+        }
+
+        #[test]
+        fn parses_visualize_before_example() {
+            let visualize = vec!["--visualize".to_string()];
+            assert_eq!(
+                solve("solve 10 --visualize --example"),
+                (InputSource::Example(None), visualize.clone())
+            );
+            assert_eq!(
+                solve("solve 10 --visualize --example 2"),
+                (InputSource::Example(Some(2)), visualize.clone())
+            );
+            assert_eq!(
+                solve("solve 10 --example --visualize"),
+                (InputSource::Example(None), visualize)
+            );
+            assert_eq!(
+                solve("solve 10 --example 2 --visualize ppm --time"),
+                (
+                    InputSource::Example(Some(2)),
+                    vec!["--visualize".into(), "ppm".into()]
+                )
+            );
+            assert_eq!(
+                solve("solve 10 --visualize png --input in.txt"),
+                (
+                    InputSource::Path("in.txt".into()),
+                    vec!["--visualize".into(), "png".into()]
+                )
+            );
+        }
+    }
 }
 
 fn main() {
-    match parse() {
+    match parse(pico_args::Arguments::from_env()) {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
+            AppArguments::All {
+                release,
+                checked,
+                time,
+                report,
+            } => all::handle(release, checked, time, report),
+            AppArguments::CheckInput { day, input } => check_input::handle(day, &input),
+            AppArguments::Compare { day, part } => compare::handle(day, part),
+            AppArguments::Gen {
+                day,
+                size,
+                seed,
+                out,
+            } => gen::handle(day, size, seed, out),
             AppArguments::Download { day } => download::handle(day),
+            AppArguments::Progress => progress::handle(),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold { day } => scaffold::handle(day),
             AppArguments::Solve {
                 day,
                 release,
+                checked,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                input,
+                debug_args,
+            } => solve::handle(day, release, checked, time, submit, &input, &debug_args),
         },
     };
 }
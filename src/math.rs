@@ -0,0 +1,221 @@
+//! Exact integer arithmetic for puzzles that invite floating point shortcuts, e.g. solving a
+//! quadratic inequality or combining cycle lengths.
+use std::ops::RangeInclusive;
+
+use crate::{Answer, Overflow};
+
+/// Integer square root, the largest `r` with `r * r <= n`.
+#[must_use]
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from above converges monotonically to the floor of the root.
+    let mut x = 1 << (n.ilog2() / 2 + 1);
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// Integers `x` with `a * x² + b * x + c < 0`, or `None` if there are none.
+///
+/// The roots are estimated with [`isqrt`] and corrected by evaluating the polynomial exactly, so
+/// integer roots are excluded. Fails with [`Overflow`] if the polynomial overflows `i128` and
+/// panics if `a` is not positive.
+pub fn quadratic_below_zero(
+    a: i128,
+    b: i128,
+    c: i128,
+) -> Result<Option<RangeInclusive<i128>>, Overflow> {
+    assert!(a > 0, "the parabola must open upwards");
+    let f = |x: i128| (Answer::from(a) * x * x + b * x + c).value();
+
+    let disc = (Answer::from(b) * b - Answer::from(4) * a * c).value()?;
+    if disc <= 0 {
+        return Ok(None);
+    }
+    let sqrt_disc = isqrt(disc as u128) as i128;
+    let two_a = (Answer::from(a) * 2).value()?;
+
+    // the estimates are off by less than 1/2a, so each bound is at most two steps away.
+    let mut lo = (Answer::from(0) - b - sqrt_disc).value()?.div_euclid(two_a);
+    if f(lo)? >= 0 {
+        lo += 1;
+    }
+    let mut hi = (Answer::from(sqrt_disc) - b).value()?.div_euclid(two_a) + 1;
+    for _ in 0..2 {
+        if f(hi)? >= 0 {
+            hi -= 1;
+        }
+    }
+
+    Ok((lo <= hi).then_some(lo..=hi))
+}
+
+/// Greatest common divisor, always non-negative.
+#[must_use]
+pub fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, always non-negative. `None` on overflow.
+#[must_use]
+pub fn lcm(a: i128, b: i128) -> Option<i128> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b).map(i128::abs)
+}
+
+/// Extended Euclidean algorithm, returns `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`.
+#[must_use]
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return if a < 0 { (-a, -1, 0) } else { (a, 1, 0) };
+    }
+    let (g, x, y) = extended_gcd(b, a.rem_euclid(b));
+    (g, y, x - a.div_euclid(b) * y)
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every `(residue, modulus)` pair with the
+/// chinese remainder theorem. Moduli need not be coprime.
+///
+/// Returns `(x, m)` such that all solutions are `x + k * m` with `0 <= x < m`, or `None` if the
+/// congruences contradict each other.
+#[must_use]
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, m), &(residue, modulus)| {
+            assert!(modulus > 0, "moduli must be positive");
+            let (g, p, _) = extended_gcd(m, modulus);
+            let diff = residue - x;
+            if diff.rem_euclid(g) != 0 {
+                return None;
+            }
+            // x + m * k ≡ residue (mod modulus)  <=>  k ≡ p * diff / g (mod modulus / g)
+            let step = modulus / g;
+            let lcm = m.checked_mul(step)?;
+            let k = ((diff / g).rem_euclid(step) * p.rem_euclid(step)).rem_euclid(step);
+            Some(((x + m * k).rem_euclid(lcm), lcm))
+        })
+}
+
+/// Computes `base ^ exp mod modulus` by repeated squaring.
+#[must_use]
+pub fn modpow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus > 0, "modulus must be positive");
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{crt, extended_gcd, gcd, isqrt, lcm, modpow, quadratic_below_zero};
+    use crate::Overflow;
+    use proptest::prelude::*;
+
+    #[test]
+    fn computes_integer_square_roots() {
+        for n in 0..10_000_u128 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({n}) = {r}");
+        }
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+        let big = 999_999_999_999_999_999_u128;
+        assert_eq!(isqrt(big * big), big);
+        assert_eq!(isqrt(big * big - 1), big - 1);
+    }
+
+    #[test]
+    fn solves_quadratic_inequalities() {
+        // (x - 10) * (x - 20) < 0, the integer roots themselves are excluded.
+        assert_eq!(quadratic_below_zero(1, -30, 200), Ok(Some(11..=19)));
+        // (x - 2)² touches zero but never goes below.
+        assert_eq!(quadratic_below_zero(1, -4, 4), Ok(None));
+        assert_eq!(quadratic_below_zero(1, 0, 1), Ok(None));
+        // roots at ±√2 and at 1.5 and 2.5.
+        assert_eq!(quadratic_below_zero(1, 0, -2), Ok(Some(-1..=1)));
+        assert_eq!(quadratic_below_zero(4, -16, 15), Ok(Some(2..=2)));
+        assert_eq!(quadratic_below_zero(4, -14, 12), Ok(None));
+
+        let t: i128 = 10_i128.pow(15);
+        assert_eq!(quadratic_below_zero(1, -t, 0), Ok(Some(1..=t - 1)));
+
+        // the discriminant or the polynomial overflow instead of wrapping.
+        assert_eq!(quadratic_below_zero(1, i128::MAX, 0), Err(Overflow));
+        assert_eq!(quadratic_below_zero(i128::MAX, 0, -1), Err(Overflow));
+    }
+
+    #[test]
+    fn computes_divisors_and_multiples() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, -6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(i128::MAX, 2), None);
+
+        for (a, b) in [(240, 46), (-7, 3), (0, 5), (5, 0), (-4, -6)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // non-coprime moduli.
+        assert_eq!(crt(&[(3, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn computes_modular_powers() {
+        assert_eq!(modpow(2, 10, 1000), 24);
+        assert_eq!(modpow(7, 0, 13), 1);
+        assert_eq!(modpow(7, 0, 1), 0);
+        assert_eq!(modpow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_quadratic_brute_force(a in 1_i128..=5, b in -60_i128..=60, c in -60_i128..=60) {
+            // every solution lies within |x| <= |b| + |c| + 1.
+            let bound = b.abs() + c.abs() + 1;
+            let below: Vec<i128> = (-bound..=bound)
+                .filter(|x| a * x * x + b * x + c < 0)
+                .collect();
+            let expected = below.first().map(|lo| *lo..=*below.last().unwrap());
+            prop_assert_eq!(quadratic_below_zero(a, b, c), Ok(expected));
+        }
+
+        #[test]
+        fn solves_random_congruences(x in 0_i128..10_000, moduli in prop::collection::vec(1_i128..50, 1..5)) {
+            let congruences: Vec<(i128, i128)> = moduli.iter().map(|m| (x % m, *m)).collect();
+            let (solution, modulus) = crt(&congruences).unwrap();
+            prop_assert_eq!(modulus, moduli.iter().fold(1, |acc, m| lcm(acc, *m).unwrap()));
+            prop_assert_eq!(solution, x % modulus);
+        }
+    }
+}
@@ -0,0 +1,401 @@
+//! Navigation of the desert network of day 8, where ghosts follow the same left/right instructions
+//! from several start nodes at once.
+//!
+//! A ghost's position only depends on its node and the index of the next instruction, so after at
+//! most `nodes * instructions` steps it repeats a state and loops forever. [`Ghost`] records where
+//! that loop starts, how long it is and at which steps the ghost stands on an end node. Steps at
+//! which all ghosts arrive together are then found with the chinese remainder theorem, without
+//! assuming that every loop starts at step zero with a single arrival.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::math;
+
+/// Nodes with interned names and the instructions to follow, parsed from the puzzle input.
+#[derive(Debug, Clone)]
+pub struct Network {
+    /// `true` for every `R`.
+    instructions: Vec<bool>,
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    /// Left and right neighbor of every node.
+    edges: Vec<[usize; 2]>,
+}
+
+impl Network {
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    #[must_use]
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    #[must_use]
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    /// Ids of all nodes whose name satisfies `predicate`.
+    pub fn find(&self, predicate: impl Fn(&str) -> bool) -> Vec<usize> {
+        (0..self.len())
+            .filter(|id| predicate(&self.names[*id]))
+            .collect()
+    }
+
+    /// The instructions, `true` for going right.
+    #[must_use]
+    pub fn instructions(&self) -> &[bool] {
+        &self.instructions
+    }
+
+    /// The node reached from `node` with the instruction at `index`, wrapping around.
+    #[must_use]
+    pub fn next(&self, node: usize, index: usize) -> usize {
+        let right = self.instructions[index % self.instructions.len()];
+        self.edges[node][usize::from(right)]
+    }
+
+    /// Follows the instructions from `start` until a state repeats.
+    #[must_use]
+    pub fn ghost(&self, start: usize, is_end: impl Fn(usize) -> bool) -> Ghost {
+        let len = self.instructions.len();
+        // step at which each (node, instruction index) state was first seen.
+        let mut seen = vec![u64::MAX; self.len() * len];
+        let mut hits = Vec::new();
+        let mut node = start;
+        let mut step: u64 = 0;
+        loop {
+            let index = (step % len as u64) as usize;
+            let state = &mut seen[node * len + index];
+            if *state != u64::MAX {
+                return Ghost {
+                    offset: *state,
+                    period: step - *state,
+                    hits,
+                };
+            }
+            *state = step;
+            if is_end(node) {
+                hits.push(step);
+            }
+            node = self.next(node, index);
+            step += 1;
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |message: String| NetworkError(message);
+        let (instructions, nodes) = s.split_once("\n\n").ok_or_else(|| {
+            error("instructions and nodes must be separated by an empty line".into())
+        })?;
+        let instructions = instructions
+            .trim()
+            .chars()
+            .map(|c| match c {
+                'L' => Ok(false),
+                'R' => Ok(true),
+                c => Err(error(format!("invalid instruction `{c}`"))),
+            })
+            .collect::<Result<Vec<bool>, _>>()?;
+        if instructions.is_empty() {
+            return Err(error("instructions must not be empty".into()));
+        }
+
+        let mut lines = Vec::new();
+        for line in nodes.lines().filter(|line| !line.is_empty()) {
+            let parsed = line
+                .split_once(" = (")
+                .and_then(|(name, rest)| Some((name, rest.strip_suffix(')')?.split_once(", ")?)));
+            lines.push(parsed.ok_or_else(|| error(format!("invalid node `{line}`")))?);
+        }
+
+        let names: Vec<String> = lines.iter().map(|(name, _)| name.to_string()).collect();
+        let mut ids = HashMap::new();
+        for (id, name) in names.iter().enumerate() {
+            if ids.insert(name.clone(), id).is_some() {
+                return Err(error(format!("node `{name}` is defined twice")));
+            }
+        }
+        let id = |name: &str| {
+            ids.get(name)
+                .copied()
+                .ok_or_else(|| error(format!("node `{name}` is never defined")))
+        };
+        let edges = lines
+            .iter()
+            .map(|(_, (left, right))| Ok([id(left)?, id(right)?]))
+            .collect::<Result<Vec<_>, NetworkError>>()?;
+
+        Ok(Network {
+            instructions,
+            names,
+            ids,
+            edges,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The arrivals of a ghost at end nodes. From step `offset` on, the ghost walks a loop of `period`
+/// steps, so every arrival at or after `offset` repeats every `period` steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ghost {
+    offset: u64,
+    period: u64,
+    hits: Vec<u64>,
+}
+
+impl Ghost {
+    /// First step of the loop.
+    #[must_use]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    #[must_use]
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+
+    /// Steps before `offset + period` at which the ghost is on an end node, ascending. Includes
+    /// step zero if the ghost starts on an end node.
+    #[must_use]
+    pub fn hits(&self) -> &[u64] {
+        &self.hits
+    }
+
+    /// Whether the ghost is on an end node after `step` steps.
+    #[must_use]
+    pub fn is_hit(&self, step: u64) -> bool {
+        let step = if step < self.offset {
+            step
+        } else {
+            self.offset + (step - self.offset) % self.period
+        };
+        self.hits.binary_search(&step).is_ok()
+    }
+
+    fn cycle_hits(&self) -> &[u64] {
+        let start = self.hits.partition_point(|hit| *hit < self.offset);
+        &self.hits[start..]
+    }
+}
+
+/// The first step after which all ghosts are on an end node at the same time, not counting the
+/// start. [`None`] if that never happens or the step does not fit a `u64`.
+#[must_use]
+pub fn first_common_arrival(ghosts: &[Ghost]) -> Option<u64> {
+    let (first, rest) = ghosts.split_first()?;
+
+    // a step before some ghost's loop has to be one of that ghost's early hits.
+    let early = ghosts
+        .iter()
+        .flat_map(|ghost| ghost.hits.iter().filter(|hit| **hit < ghost.offset))
+        .filter(|step| **step > 0)
+        .filter(|step| ghosts.iter().all(|ghost| ghost.is_hit(**step)))
+        .min();
+    if early.is_some() {
+        return early.copied();
+    }
+
+    // otherwise every ghost is in its loop, so the step is congruent to one of its loop hits.
+    let to_congruence = |ghost: &Ghost, hit: &u64| (i128::from(*hit), i128::from(ghost.period));
+    let mut solutions: Vec<(i128, i128)> = first
+        .cycle_hits()
+        .iter()
+        .map(|hit| to_congruence(first, hit))
+        .collect();
+    for ghost in rest {
+        let mut merged: Vec<(i128, i128)> = solutions
+            .iter()
+            .flat_map(|solution| {
+                ghost
+                    .cycle_hits()
+                    .iter()
+                    .filter_map(move |hit| math::crt(&[*solution, to_congruence(ghost, hit)]))
+            })
+            .collect();
+        merged.sort_unstable();
+        merged.dedup();
+        solutions = merged;
+    }
+
+    let earliest = ghosts.iter().map(|ghost| ghost.offset).max()?.max(1);
+    solutions
+        .into_iter()
+        .filter_map(|(x, m)| {
+            // the smallest solution x + k * m that is not before any loop starts.
+            let below = (i128::from(earliest) - x).max(0);
+            let k = (below + m - 1) / m;
+            u64::try_from(x.checked_add(k.checked_mul(m)?)?).ok()
+        })
+        .min()
+}
+
+/// An error which can be returned when parsing a network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkError(String);
+
+impl Error for NetworkError {}
+
+impl Display for NetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{first_common_arrival, Ghost, Network, NetworkError};
+    use proptest::prelude::*;
+
+    const GHOSTS: &str = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    fn ghosts(network: &Network) -> Vec<Ghost> {
+        network
+            .find(|name| name.ends_with('A'))
+            .into_iter()
+            .map(|start| network.ghost(start, |node| network.name(node).ends_with('Z')))
+            .collect()
+    }
+
+    #[test]
+    fn follows_instructions() {
+        let network: Network = "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)"
+            .parse()
+            .unwrap();
+        let end = network.id("ZZZ").unwrap();
+        let ghost = network.ghost(network.id("AAA").unwrap(), |node| node == end);
+        assert_eq!(ghost.hits().first(), Some(&6));
+        assert_eq!(first_common_arrival(&[ghost]), Some(6));
+    }
+
+    #[test]
+    fn analyzes_cycles() {
+        let network: Network = GHOSTS.parse().unwrap();
+        let ghosts = ghosts(&network);
+        // 11A loops through 11B and 11Z from step 1, 22A through 22B to 22Z from step 1.
+        assert_eq!((ghosts[0].offset(), ghosts[0].period()), (1, 2));
+        assert_eq!(ghosts[0].hits(), &[2]);
+        assert_eq!((ghosts[1].offset(), ghosts[1].period()), (1, 6));
+        assert_eq!(ghosts[1].hits(), &[3, 6]);
+        assert!(ghosts[1].is_hit(3_000_000_000_003));
+        assert_eq!(first_common_arrival(&ghosts), Some(6));
+    }
+
+    #[test]
+    fn handles_unclean_cycles() {
+        // the ghost from 1A arrives after 2 steps and then every 3 steps, the one from 2A after
+        // 3 steps and every 2 steps. Taking the lcm of the first arrivals would give 6.
+        let network: Network = "L
+
+1A = (1B, 1B)
+1B = (1Z, 1Z)
+1Z = (1C, 1C)
+1C = (1D, 1D)
+1D = (1Z, 1Z)
+2A = (2B, 2B)
+2B = (2C, 2C)
+2C = (2Z, 2Z)
+2Z = (2D, 2D)
+2D = (2Z, 2Z)"
+            .parse()
+            .unwrap();
+        assert_eq!(first_common_arrival(&ghosts(&network)), Some(5));
+
+        // the first ghost only arrives at odd steps, the second only at even ones.
+        let network: Network =
+            "L\n\n1A = (1Z, 1Z)\n1Z = (1A, 1A)\n2A = (2B, 2B)\n2B = (2Z, 2Z)\n2Z = (2B, 2B)"
+                .parse()
+                .unwrap();
+        assert_eq!(first_common_arrival(&ghosts(&network)), None);
+        assert_eq!(first_common_arrival(&[]), None);
+    }
+
+    #[test]
+    fn rejects_invalid_networks() {
+        let err = |s: &str| s.parse::<Network>().unwrap_err();
+        assert_eq!(
+            err("LR\nAAA = (AAA, AAA)"),
+            NetworkError("instructions and nodes must be separated by an empty line".into())
+        );
+        assert_eq!(
+            err("LX\n\nAAA = (AAA, AAA)"),
+            NetworkError("invalid instruction `X`".into())
+        );
+        assert_eq!(
+            err("L\n\nAAA = (BBB, AAA)"),
+            NetworkError("node `BBB` is never defined".into())
+        );
+        assert_eq!(
+            err("L\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)"),
+            NetworkError("node `AAA` is defined twice".into())
+        );
+        assert_eq!(
+            err("L\n\nAAA = AAA"),
+            NetworkError("invalid node `AAA = AAA`".into())
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn matches_simulation(
+            edges in prop::collection::vec((0_usize..6, 0_usize..6), 6),
+            instructions in prop::collection::vec(any::<bool>(), 1..5),
+            ends in prop::collection::vec(any::<bool>(), 6),
+            starts in prop::collection::vec(0_usize..6, 1..4),
+        ) {
+            let lines: Vec<String> = edges
+                .iter()
+                .enumerate()
+                .map(|(i, (l, r))| format!("N{i} = (N{l}, N{r})"))
+                .collect();
+            let instructions: String = instructions.iter().map(|r| if *r { 'R' } else { 'L' }).collect();
+            let network: Network = format!("{instructions}\n\n{}", lines.join("\n")).parse().unwrap();
+
+            let ghosts: Vec<Ghost> = starts
+                .iter()
+                .map(|start| network.ghost(*start, |node| ends[node]))
+                .collect();
+
+            // with at most 24 states per ghost, any common arrival happens within 24 + 24³ steps.
+            let mut nodes = starts.clone();
+            let mut expected = None;
+            for step in 1..=24 + 24_u64.pow(3) {
+                let index = (step - 1) as usize;
+                for node in &mut nodes {
+                    *node = network.next(*node, index);
+                }
+                if nodes.iter().all(|node| ends[*node]) {
+                    expected = Some(step);
+                    break;
+                }
+            }
+            prop_assert_eq!(first_common_arrival(&ghosts), expected);
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Polynomial extrapolation from sampled values, e.g. to continue a sequence or to evaluate a
+//! step count that grows quadratically far beyond what can be simulated.
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::math;
+use crate::{Answer, Overflow};
+
+/// The polynomial through equally spaced samples `f(0), f(1), …`, stored as the leading forward
+/// differences `Δᵏf(0)` of Newton's forward difference formula.
+///
+/// ```
+/// # use advent_of_code::{poly::Newton, Answer};
+/// let squares = Newton::from_samples(&[0, 1, 4, 9]).unwrap();
+/// assert_eq!(squares.differences(), &[0, 1, 2]);
+/// assert_eq!(squares.eval(-3), Answer::from(9));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Newton {
+    differences: Vec<i128>,
+}
+
+impl Newton {
+    /// Builds the difference table of `samples` until a row is all zeros, so the degree is the
+    /// lowest one that fits. Fails with [`Overflow`] if the differences overflow `i128`.
+    pub fn from_samples(samples: &[i128]) -> Result<Self, Overflow> {
+        let mut row = samples.to_vec();
+        let mut differences = Vec::new();
+        while row.iter().any(|x| *x != 0) {
+            differences.push(row[0]);
+            row = row
+                .windows(2)
+                .map(|pair| pair[1].checked_sub(pair[0]).ok_or(Overflow))
+                .collect::<Result<_, _>>()?;
+        }
+        Ok(Newton { differences })
+    }
+
+    /// Leading forward differences, one per degree. Empty for the zero polynomial.
+    #[must_use]
+    pub fn differences(&self) -> &[i128] {
+        &self.differences
+    }
+
+    /// Evaluates the polynomial at any integer `x`, including negative ones. Large values of `x`
+    /// are fine as long as the result fits an [`Answer`].
+    #[must_use]
+    pub fn eval(&self, x: i128) -> Answer {
+        // f(x) = Σ Δᵏf(0) * C(x, k), the generalized binomial coefficient is always an integer.
+        let mut binomial = Answer::from(1);
+        let mut value = Answer::from(0);
+        for (k, difference) in (0..).zip(&self.differences) {
+            if k > 0 {
+                binomial = binomial * (x - k + 1) / k;
+            }
+            value += binomial * *difference;
+        }
+        value
+    }
+}
+
+/// Evaluates the polynomial of lowest degree through `points` at `x` with Lagrange interpolation.
+/// The points can be spaced arbitrarily but need distinct x coordinates.
+///
+/// ```
+/// # use advent_of_code::poly::{lagrange, Rational};
+/// assert_eq!(lagrange(&[(0, 0), (2, 1)], 1), Rational::new(1, 2));
+/// assert_eq!(lagrange(&[(1, 1), (2, 4), (5, 25)], 100).to_integer(), Some(10_000));
+/// ```
+#[must_use]
+pub fn lagrange(points: &[(i128, i128)], x: i128) -> Rational {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &(xi, yi))| {
+            points.iter().enumerate().filter(|(j, _)| *j != i).fold(
+                Rational::from(yi),
+                |acc, (_, &(xj, _))| {
+                    assert!(xi != xj, "x coordinates must be distinct, got {xi} twice");
+                    acc * Rational::new(x - xj, xi - xj)
+                },
+            )
+        })
+        .fold(Rational::from(0), |acc, term| acc + term)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An exact fraction in lowest terms with a positive denominator.
+/// Arithmetic panics on overflow in debug builds like the primitive integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    /// Panics if `denom` is zero.
+    #[must_use]
+    pub fn new(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "denominator must not be zero");
+        let sign = denom.signum();
+        let divisor = math::gcd(numer, denom);
+        Rational {
+            numer: sign * numer / divisor,
+            denom: sign * denom / divisor,
+        }
+    }
+
+    #[must_use]
+    pub fn numer(self) -> i128 {
+        self.numer
+    }
+
+    #[must_use]
+    pub fn denom(self) -> i128 {
+        self.denom
+    }
+
+    /// Returns the value if it is a whole number.
+    #[must_use]
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denom == 1).then_some(self.numer)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Rational {
+            numer: value,
+            denom: 1,
+        }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.to_integer() {
+            Some(value) => write!(f, "{value}"),
+            None => write!(f, "{}/{}", self.numer, self.denom),
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Rational) -> Rational {
+        let denom = math::lcm(self.denom, rhs.denom).expect("denominator overflow");
+        Rational::new(
+            self.numer * (denom / self.denom) + rhs.numer * (denom / rhs.denom),
+            denom,
+        )
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Rational) -> Rational {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Rational) -> Rational {
+        // cancel crosswise first to keep the intermediate products small.
+        let a = Rational::new(self.numer, rhs.denom);
+        let b = Rational::new(rhs.numer, self.denom);
+        Rational::new(a.numer * b.numer, a.denom * b.denom)
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: Rational) -> Rational {
+        self.mul(Rational::new(rhs.denom, rhs.numer))
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{lagrange, Newton, Rational};
+    use crate::{Answer, Overflow};
+    use proptest::prelude::*;
+
+    #[test]
+    fn builds_difference_tables() {
+        assert_eq!(
+            Newton::from_samples(&[1, 3, 6, 10, 15])
+                .unwrap()
+                .differences(),
+            &[1, 2, 1]
+        );
+        assert_eq!(
+            Newton::from_samples(&[5, 5, 5]).unwrap().differences(),
+            &[5]
+        );
+        assert!(Newton::from_samples(&[0, 0])
+            .unwrap()
+            .differences()
+            .is_empty());
+        assert!(Newton::from_samples(&[]).unwrap().differences().is_empty());
+        // without a zero row, all samples are needed.
+        assert_eq!(
+            Newton::from_samples(&[1, 2, 4]).unwrap().differences(),
+            &[1, 1, 1]
+        );
+        assert_eq!(Newton::from_samples(&[i128::MIN, i128::MAX]), Err(Overflow));
+    }
+
+    #[test]
+    fn extrapolates_in_both_directions() {
+        let triangular = Newton::from_samples(&[1, 3, 6, 10, 15]).unwrap();
+        assert_eq!(triangular.eval(5), Answer::from(21));
+        assert_eq!(triangular.eval(-1), Answer::from(0));
+        assert_eq!(triangular.eval(-2), Answer::from(0));
+        assert_eq!(
+            Newton::from_samples(&[0, 0]).unwrap().eval(7),
+            Answer::from(0)
+        );
+
+        let x = 10_i128.pow(12);
+        assert_eq!(triangular.eval(x - 1), Answer::from(x * (x + 1) / 2));
+        let cubes = Newton::from_samples(&[0, 1, 8, 27]).unwrap();
+        assert_eq!(cubes.eval(10_i128.pow(12)).value(), Ok(10_i128.pow(36)));
+        assert!(cubes.eval(10_i128.pow(13)).value().is_err());
+    }
+
+    #[test]
+    fn interpolates_exactly() {
+        // 3x² - x + 7 through unevenly spaced points.
+        let points = [(-2, 21), (1, 9), (5, 77)];
+        assert_eq!(lagrange(&points, 0), Rational::from(7));
+        assert_eq!(
+            lagrange(&points, 26_501_365).to_integer(),
+            Some(3 * 26_501_365_i128.pow(2) - 26_501_365 + 7)
+        );
+        assert_eq!(lagrange(&[(0, 1), (3, 2)], 1), Rational::new(4, 3));
+        assert_eq!(lagrange(&[(4, 9)], -100), Rational::from(9));
+    }
+
+    #[test]
+    fn reduces_fractions() {
+        assert_eq!(Rational::new(6, -4), Rational::new(-3, 2));
+        assert_eq!(Rational::new(6, -4).denom(), 2);
+        assert_eq!(Rational::new(0, -5), Rational::from(0));
+        assert_eq!(
+            Rational::new(1, 2) + Rational::new(1, 3),
+            Rational::new(5, 6)
+        );
+        assert_eq!(Rational::new(1, 2) - Rational::new(1, 2), Rational::from(0));
+        assert_eq!(
+            Rational::new(2, 3) * Rational::new(9, 4),
+            Rational::new(3, 2)
+        );
+        assert_eq!(
+            Rational::new(2, 3) / Rational::new(-4, 9),
+            Rational::new(-3, 2)
+        );
+        assert_eq!(Rational::new(-3, 2).to_string(), "-3/2");
+        assert_eq!(Rational::new(4, 2).to_string(), "2");
+    }
+
+    proptest! {
+        #[test]
+        fn newton_and_lagrange_agree(
+            coefficients in prop::collection::vec(-100_i128..=100, 1..5),
+            x in -1000_i128..=1000,
+        ) {
+            let f = |x: i128| coefficients.iter().rev().fold(0, |acc, c| acc * x + c);
+            let samples: Vec<i128> = (0..coefficients.len() as i128).map(f).collect();
+            let points: Vec<(i128, i128)> = (0..coefficients.len() as i128)
+                .map(|x| (3 * x - 7, f(3 * x - 7)))
+                .collect();
+
+            prop_assert_eq!(Newton::from_samples(&samples).unwrap().eval(x), Answer::from(f(x)));
+            prop_assert_eq!(lagrange(&points, x), Rational::from(f(x)));
+        }
+    }
+}
@@ -2,11 +2,17 @@ use std::io;
 
 use crate::template::{
     readme_benchmarks::{self, Timings},
+    report::{self, ReportFormat},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 use crate::{all_days, Day};
 
-pub fn handle(is_release: bool, is_timed: bool) {
+pub fn handle(
+    is_release: bool,
+    is_checked: bool,
+    is_timed: bool,
+    report: Option<(ReportFormat, String)>,
+) {
     let mut timings: Vec<Timings> = vec![];
 
     all_days().for_each(|day| {
@@ -17,7 +23,7 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = child_commands::run_solution(day, is_timed, is_release, is_checked).unwrap();
 
         if output.is_empty() {
             println!("Not solved.");
@@ -32,7 +38,15 @@ pub fn handle(is_release: bool, is_timed: bool) {
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
+        if let Some((format, path)) = report {
+            match report::write(format, &path, timings, total_millis) {
+                Ok(()) => println!("Successfully wrote benchmark report to \"{path}\"."),
+                Err(_) => {
+                    eprintln!("Failed to write benchmark report to \"{path}\".");
+                }
+            }
+        } else if is_release && !is_checked {
+            // overflow checks slow down solutions, so these timings are not written to the readme.
             match readme_benchmarks::update(timings, total_millis) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
@@ -63,8 +77,9 @@ pub fn get_path_for_bin(day: Day) -> String {
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
+pub(crate) mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::memory::MemoryUsage;
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
@@ -74,7 +89,12 @@ mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        is_checked: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -83,10 +103,19 @@ mod child_commands {
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
-        if is_release {
+        if is_checked {
+            args.push("--profile");
+            args.push("checked");
+        } else if is_release {
             args.push("--release");
         }
 
+        if cfg!(feature = "memory") {
+            // mirror `memory` feature to child invocations.
+            args.push("--features");
+            args.push("memory");
+        }
+
         if is_timed {
             // mirror `--time` flag to child invocations.
             args.push("--");
@@ -130,6 +159,10 @@ mod child_commands {
             day,
             part_1: None,
             part_2: None,
+            part_1_nanos: None,
+            part_2_nanos: None,
+            part_1_memory: None,
+            part_2_memory: None,
             total_nanos: 0_f64,
         };
 
@@ -151,13 +184,24 @@ mod child_commands {
             .for_each(|(part, timing_str, nanos)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_nanos = Some(nanos);
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_nanos = Some(nanos);
                 }
 
                 timings.total_nanos += nanos;
             });
 
+        // memory usage is reported on a tagged line of its own, see `MemoryUsage::record`.
+        for (part, usage) in output.iter().filter_map(|l| MemoryUsage::parse_record(l)) {
+            match part {
+                1 => timings.part_1_memory = Some(usage.to_string()),
+                2 => timings.part_2_memory = Some(usage.to_string()),
+                _ => {}
+            }
+        }
+
         timings
     }
 
@@ -171,7 +215,7 @@ mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -187,7 +231,7 @@ mod child_commands {
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     macro_rules! assert_approx_eq {
         ($a:expr, $b:expr) => {{
             let (a, b) = (&$a, &$b);
@@ -200,11 +244,13 @@ mod child_commands {
         }};
     }
 
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_time};
+        use std::time::Duration;
 
         use crate::day;
+        use proptest::prelude::*;
 
         #[test]
         fn test_well_formed() {
@@ -236,6 +282,35 @@ mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn test_memory_usage() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 1 memory: peak_bytes=1536 allocations=12".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            assert_eq!(res.part_1_memory.unwrap(), "1.5 KiB peak, 12 allocs");
+            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert!(res.part_2_memory.is_none());
+
+            // the memory record does not depend on the format of the timing.
+            let res = parse_exec_time(
+                &[
+                    "Part 2: ✖        ".into(),
+                    "Part 2 memory: peak_bytes=10 allocations=1".into(),
+                ],
+                day!(1),
+            );
+            assert!(res.part_2.is_none());
+            assert_eq!(res.part_2_memory.unwrap(), "10 B peak, 1 allocs");
+        }
+
         #[test]
         fn test_missing_parts() {
             let res = parse_exec_time(
@@ -247,8 +322,39 @@ mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
+        }
+
+        proptest! {
+            #[test]
+            fn parses_every_duration_format(
+                nanos in prop_oneof![0_u64..1_000, 0_u64..1_000_000, 0_u64..1_000_000_000, 0_u64..100_000_000_000],
+                precision in proptest::option::of(0_usize..=4),
+                samples in 1_u32..100_000,
+            ) {
+                let duration = Duration::from_nanos(nanos);
+                let formatted = match precision {
+                    Some(precision) => format!("{duration:.precision$?}"),
+                    None => format!("{duration:?}"),
+                };
+                let line = format!("Part 1: 42 ({formatted} @ {samples} samples)");
+                let (timing_str, parsed) = parse_time(&line).unwrap();
+                prop_assert_eq!(timing_str, formatted.as_str());
+
+                // the value can be off by the rounding of the last printed digit.
+                let unit = [("ns", 1.0), ("µs", 1e3), ("ms", 1e6), ("s", 1e9)]
+                    .iter()
+                    .find(|(suffix, _)| formatted.ends_with(suffix))
+                    .map(|(_, unit)| *unit)
+                    .unwrap();
+                let decimals = formatted.split('.').nth(1).map_or(0, |d| d.trim_end_matches(char::is_alphabetic).len());
+                let tolerance = unit * 0.5 * 10_f64.powi(-(decimals as i32)) + 1e-6;
+                prop_assert!(
+                    (parsed - nanos as f64).abs() <= tolerance,
+                    "parsed {} from {} for {}ns", parsed, formatted, nanos
+                );
+            }
         }
     }
 }
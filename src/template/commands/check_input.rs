@@ -0,0 +1,53 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use super::all::get_path_for_bin;
+use crate::template::{InputSource, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Day};
+
+pub fn handle(day: Option<Day>, input: &InputSource) {
+    let days: Vec<Day> = match day {
+        Some(day) => vec![day],
+        None => all_days().collect(),
+    };
+
+    let mut failed: Vec<Day> = vec![];
+
+    for day in days {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+
+        let mut cmd_args = vec![
+            "run".to_string(),
+            "--quiet".to_string(),
+            "--bin".to_string(),
+            day.to_string(),
+            "--".to_string(),
+            "--check-input".to_string(),
+        ];
+        cmd_args.append(&mut input.to_args());
+
+        let status = Command::new("cargo")
+            .args(&cmd_args)
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .unwrap();
+
+        if !status.success() {
+            failed.push(day);
+        }
+    }
+
+    if !failed.is_empty() {
+        let days: Vec<String> = failed.iter().map(Day::to_string).collect();
+        eprintln!("---");
+        eprintln!("Input assumptions violated for day(s): {}", days.join(", "));
+        std::process::exit(1);
+    }
+}
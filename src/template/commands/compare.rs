@@ -0,0 +1,28 @@
+use std::process::{Command, Stdio};
+
+use crate::Day;
+
+pub fn handle(day: Day, part: Option<u8>) {
+    // comparisons are only meaningful for optimized builds.
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--compare".to_string(),
+    ];
+
+    if let Some(part) = part {
+        cmd_args.push(part.to_string());
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
@@ -0,0 +1,48 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use rand::Rng;
+
+use crate::gen::{generate, DEFAULT_SIZE};
+use crate::Day;
+
+pub fn handle(day: Day, size: Option<usize>, seed: Option<u64>, out: Option<PathBuf>) {
+    let size = size.unwrap_or(DEFAULT_SIZE);
+    // print the seed so that interesting inputs can be generated again.
+    let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let path = out.unwrap_or_else(|| PathBuf::from(format!("data/generated/{day}.txt")));
+
+    let input = match generate(day, size, seed) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to generate input for day {day} (size {size}): {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Some(dir) = path.parent().filter(|dir| dir != &Path::new("")) {
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!("Failed to create directory \"{}\": {e}", dir.display());
+            process::exit(1);
+        }
+    }
+
+    match fs::write(&path, input + "\n") {
+        Ok(()) => {
+            println!(
+                "Generated input for day {day} (size {size}, seed {seed}) at \"{}\"",
+                path.display()
+            );
+            println!("---");
+            println!(
+                "🎄 Run it with: cargo solve {day} --input {}",
+                path.display()
+            );
+        }
+        Err(e) => {
+            eprintln!("Failed to write input: {e}");
+            process::exit(1);
+        }
+    }
+}
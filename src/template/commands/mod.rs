@@ -1,5 +1,9 @@
 pub mod all;
+pub mod check_input;
+pub mod compare;
 pub mod download;
+pub mod gen;
+pub mod progress;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
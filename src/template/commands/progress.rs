@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use super::all::{child_commands, get_path_for_bin};
+use crate::all_days;
+use crate::template::readme_progress::{self, Progress, Star};
+use crate::template::{aoc_cli, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle() {
+    let Some(year) = aoc_cli::get_year() else {
+        eprintln!("Could not determine the year, set `AOC_YEAR` in `.cargo/config.toml`.");
+        process::exit(1);
+    };
+
+    let mut progress: Vec<Progress> = vec![];
+
+    for day in all_days() {
+        // days that have not been scaffolded yet are left out of the table.
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        let output = child_commands::run_solution(day, false, true, false).unwrap();
+        let answers = parse_answers(&output);
+        // the puzzle description lists accepted answers if it was downloaded after solving.
+        let accepted = fs::read_to_string(aoc_cli::get_puzzle_path(day))
+            .map(|puzzle| readme_progress::parse_accepted_answers(&puzzle))
+            .unwrap_or_default();
+
+        let star = |part: usize| {
+            Star::new(
+                answers[part].as_deref(),
+                accepted.get(part).map(String::as_str),
+            )
+        };
+        progress.push(Progress {
+            day,
+            part_1: star(0),
+            part_2: star(1),
+        });
+    }
+
+    match readme_progress::update(year, &progress) {
+        Ok(()) => println!("Successfully updated README with progress."),
+        Err(_) => {
+            eprintln!("Failed to update readme with progress.");
+        }
+    }
+}
+
+/// Extracts the answers of both parts from the output of a solution binary.
+/// Parts without a single-line answer, e.g. returning `None`, are [`None`].
+fn parse_answers(output: &[String]) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+    for line in output {
+        // the runner prints an intermediate result first and overwrites it with a carriage return.
+        let line = line.rsplit('\r').next().unwrap_or_default();
+        let part = match line.split(':').next() {
+            Some("Part 1") => 0,
+            Some("Part 2") => 1,
+            _ => continue,
+        };
+        answers[part] = line
+            .split_once(ANSI_BOLD)
+            .and_then(|(_, rest)| rest.split_once(ANSI_RESET))
+            .map(|(answer, _)| answer.to_string());
+    }
+    answers
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::parse_answers;
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    #[test]
+    fn parses_answers() {
+        let output = vec![
+            format!(
+                "Part 1: {ANSI_BOLD}142{ANSI_RESET}\rPart 1: {ANSI_BOLD}142{ANSI_RESET} (1.2ms)"
+            ),
+            "Part 2: ✖\rPart 2: ✖             ".to_string(),
+        ];
+        assert_eq!(parse_answers(&output), [Some("142".into()), None]);
+
+        // multi-line answers can not be submitted.
+        let output = vec![
+            "Part 1: ▼ (10.0µs)".to_string(),
+            "#..#".to_string(),
+            "Part 2: ✖ arithmetic overflow".to_string(),
+        ];
+        assert_eq!(parse_answers(&output), [None, None]);
+        assert_eq!(parse_answers(&[]), [None, None]);
+    }
+}
@@ -1,14 +1,32 @@
 use std::process::{Command, Stdio};
 
+use crate::template::InputSource;
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    checked: bool,
+    time: bool,
+    submit_part: Option<u8>,
+    input: &InputSource,
+    debug_args: &[String],
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
-    if release {
+    if checked {
+        // optimized build that panics on arithmetic overflow.
+        cmd_args.push("--profile".to_string());
+        cmd_args.push("checked".to_string());
+    } else if release {
         cmd_args.push("--release".to_string());
     }
 
+    if cfg!(feature = "memory") {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("memory".to_string());
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -20,8 +38,13 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
+    // stdin is inherited by the child process for `--input -`.
+    cmd_args.append(&mut input.to_args());
+    cmd_args.extend_from_slice(debug_args);
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
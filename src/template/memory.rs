@@ -0,0 +1,210 @@
+/// Module that tracks heap usage of solutions.
+/// When the `memory` feature is enabled, a counting global allocator is installed that records
+/// the current and peak number of allocated bytes as well as the number of allocations.
+use std::fmt::Display;
+
+#[cfg(feature = "memory")]
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Peak number of heap bytes allocated on top of what was allocated before the measurement.
+    pub peak_bytes: usize,
+    /// Number of allocations (including reallocations) performed during the measurement.
+    pub allocations: usize,
+}
+
+impl MemoryUsage {
+    /// Formats the usage of `part` as a tagged line, e.g.
+    /// `Part 1 memory: peak_bytes=1536 allocations=12`, that [`MemoryUsage::parse_record`] reads.
+    #[must_use]
+    pub fn record(&self, part: u8) -> String {
+        format!(
+            "Part {part} memory: peak_bytes={} allocations={}",
+            self.peak_bytes, self.allocations
+        )
+    }
+
+    /// Parses a line written by [`MemoryUsage::record`], returning the part and its usage.
+    #[must_use]
+    pub fn parse_record(line: &str) -> Option<(u8, MemoryUsage)> {
+        let (part, fields) = line.strip_prefix("Part ")?.split_once(" memory: ")?;
+        let (mut peak_bytes, mut allocations) = (None, None);
+        for field in fields.split_whitespace() {
+            match field.split_once('=')? {
+                ("peak_bytes", value) => peak_bytes = value.parse().ok(),
+                ("allocations", value) => allocations = value.parse().ok(),
+                _ => {}
+            }
+        }
+        let usage = MemoryUsage {
+            peak_bytes: peak_bytes?,
+            allocations: allocations?,
+        };
+        Some((part.parse().ok()?, usage))
+    }
+}
+
+impl Display for MemoryUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} peak, {} allocs",
+            format_bytes(self.peak_bytes),
+            self.allocations
+        )
+    }
+}
+
+#[must_use]
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next_unit in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{value:.1} {unit}")
+}
+
+#[cfg(feature = "memory")]
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "memory")]
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "memory")]
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator that forwards to [`System`] while counting bytes and allocations.
+#[cfg(feature = "memory")]
+pub struct CountingAllocator;
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[cfg(feature = "memory")]
+fn record_alloc(size: usize) {
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(feature = "memory")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `func` and returns its result together with the heap usage it caused.
+#[cfg(feature = "memory")]
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, MemoryUsage) {
+    let baseline = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+    let allocations = ALLOCATIONS.load(Ordering::SeqCst);
+
+    let result = func();
+
+    let usage = MemoryUsage {
+        peak_bytes: PEAK.load(Ordering::SeqCst).saturating_sub(baseline),
+        allocations: ALLOCATIONS.load(Ordering::SeqCst) - allocations,
+    };
+
+    (result, usage)
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{format_bytes, MemoryUsage};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.0 KiB");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn displays_usage() {
+        let usage = MemoryUsage {
+            peak_bytes: 2048,
+            allocations: 12,
+        };
+        assert_eq!(usage.to_string(), "2.0 KiB peak, 12 allocs");
+    }
+
+    #[test]
+    fn parses_records() {
+        let usage = MemoryUsage {
+            peak_bytes: 2048,
+            allocations: 12,
+        };
+        assert_eq!(
+            usage.record(2),
+            "Part 2 memory: peak_bytes=2048 allocations=12"
+        );
+        assert_eq!(
+            MemoryUsage::parse_record(&usage.record(2)),
+            Some((2, usage))
+        );
+        assert_eq!(
+            MemoryUsage::parse_record("Part 1 memory: allocations=3 peak_bytes=10"),
+            Some((
+                1,
+                MemoryUsage {
+                    peak_bytes: 10,
+                    allocations: 3
+                }
+            ))
+        );
+        assert_eq!(
+            MemoryUsage::parse_record("Part 1 memory: peak_bytes=10"),
+            None
+        );
+        assert_eq!(MemoryUsage::parse_record("Part 1: 42 (1.2ms)"), None);
+    }
+}
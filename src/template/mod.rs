@@ -1,36 +1,268 @@
 use crate::Day;
-use std::{env, fs};
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::{env, fs, process};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod memory;
 pub mod readme_benchmarks;
+pub mod readme_progress;
+pub mod report;
 pub mod runner;
+pub mod stats;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+fn get_data_path(folder: &str, file_name: &str) -> PathBuf {
+    let cwd = env::current_dir().unwrap();
+    cwd.join("data").join(folder).join(file_name)
+}
+
+fn read_path(filepath: &PathBuf) -> String {
+    fs::read_to_string(filepath)
+        .unwrap_or_else(|e| panic!("could not open input file \"{}\": {e}", filepath.display()))
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_path(&get_data_path(folder, &format!("{day}.txt")))
+}
+
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01_2.txt`.
+#[must_use]
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    read_path(&get_data_path(folder, &format!("{day}_{part}.txt")))
+}
+
+/// The source a solution binary reads its puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `data/inputs/<day>.txt`, used if no other source is specified.
+    Inputs,
+    /// `data/examples/<day>.txt`, or `data/examples/<day>_<n>.txt` if a number is given.
+    Example(Option<u8>),
+    /// An arbitrary file passed via `--input <path>`.
+    Path(PathBuf),
+    /// Standard input, selected via `--input -`.
+    Stdin,
+}
+
+impl InputSource {
+    /// Parses `--input <path>`, `--input -` and `--example [n]` from command-line arguments.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let input_index = args.iter().position(|x| x == "--input");
+        let example_index = args.iter().position(|x| x == "--example");
+
+        match (input_index, example_index) {
+            (Some(_), Some(_)) => Err("`--input` and `--example` can not be combined.".into()),
+            (Some(i), None) => match args.get(i + 1).map(String::as_str) {
+                Some("-") => Ok(InputSource::Stdin),
+                Some(path) if !path.starts_with("--") => Ok(InputSource::Path(path.into())),
+                _ => Err("Unexpected command-line input. Format: --input <path|->".into()),
+            },
+            (None, Some(i)) => match args.get(i + 1) {
+                Some(part) if !part.starts_with("--") => part
+                    .parse()
+                    .map(|part| InputSource::Example(Some(part)))
+                    .map_err(|_| "Unexpected command-line input. Format: --example [n]".into()),
+                _ => Ok(InputSource::Example(None)),
+            },
+            (None, None) => Ok(InputSource::Inputs),
+        }
+    }
+
+    /// Command-line arguments that select this source in a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Inputs => vec![],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(part)) => vec!["--example".into(), part.to_string()],
+            InputSource::Path(path) => vec!["--input".into(), path.display().to_string()],
+            InputSource::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// Path of the file that is read for `day`, [`None`] for standard input.
+    #[must_use]
+    pub fn path(&self, day: Day) -> Option<PathBuf> {
+        match self {
+            InputSource::Inputs => Some(get_data_path("inputs", &format!("{day}.txt"))),
+            InputSource::Example(None) => Some(get_data_path("examples", &format!("{day}.txt"))),
+            InputSource::Example(Some(part)) => {
+                Some(get_data_path("examples", &format!("{day}_{part}.txt")))
+            }
+            InputSource::Path(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// Reads the input for `day` from this source.
+    pub fn read(&self, day: Day) -> Result<String, String> {
+        match self.path(day) {
+            Some(path) => fs::read_to_string(&path)
+                .map_err(|e| format!("could not open input file \"{}\": {e}", path.display())),
+            None => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("could not read input from stdin: {e}"))?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Reads the puzzle input for `day` from the source selected on the command-line.
+/// Exits with an error message if the input can not be read.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+    match InputSource::from_args(&args).and_then(|source| source.read(day)) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    }
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// Alternative implementations of a part can be registered for `--compare`, e.g.
+/// `solution!(1, compare: [1: part_one_old => part_one])` benches `part_one` against `part_one_old`.
+///
+/// Assumptions about the input can be declared with a validation function that is run by
+/// `--check-input` and before solving, e.g. `solution!(10, validate: validate)`.
+///
+/// Simulation days can register a constructor for the stepping debugger that is launched by
+/// `--step`, e.g. `solution!(14, step: simulation)`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (
+        $day:expr
+        $(, compare: [$($part:literal: $baseline:path => $candidate:path),* $(,)?])?
+        $(, validate: $validate:path)?
+        $(, step: $step:path)?
+        $(,)?
+    ) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
+            let input = advent_of_code::template::read_input(DAY);
+
+            let validate: Option<fn(&str) -> advent_of_code::validate::Validation> =
+                None $(.or(Some($validate as _)))?;
+            validate_input(validate, &input);
+
+            if advent_of_code::debugger::requested() {
+                #[allow(unused_mut)]
+                let mut stepped = false;
+                $(
+                    advent_of_code::debugger::run($step(&input));
+                    stepped = true;
+                )?
+                if !stepped {
+                    eprintln!("No simulation registered for stepping.");
+                    std::process::exit(1);
+                }
+                return;
+            }
+
+            if let Some(compare_part) = compare_arg() {
+                #[allow(unused_mut)]
+                let mut compared = false;
+                $($(
+                    if compare_part.map_or(true, |part| part == $part) {
+                        compare_parts(
+                            (stringify!($baseline), $baseline),
+                            (stringify!($candidate), $candidate),
+                            &input,
+                            $part,
+                        );
+                        compared = true;
+                    }
+                )*)?
+                if !compared {
+                    eprintln!("No variants registered for comparison.");
+                    std::process::exit(1);
+                }
+                return;
+            }
+
             run_part(part_one, &input, DAY, 1);
             run_part(part_two, &input, DAY, 2);
         }
     };
 }
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::InputSource;
+    use crate::day;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(Into::into).collect()
+    }
+
+    #[test]
+    fn defaults_to_inputs() {
+        let source = InputSource::from_args(&args("target/debug/01 --time")).unwrap();
+        assert_eq!(source, InputSource::Inputs);
+        assert!(source
+            .path(day!(1))
+            .unwrap()
+            .ends_with("data/inputs/01.txt"));
+    }
+
+    #[test]
+    fn parses_input_path() {
+        let source = InputSource::from_args(&args("01 --input /tmp/foo.txt --time")).unwrap();
+        assert_eq!(source, InputSource::Path("/tmp/foo.txt".into()));
+        assert_eq!(
+            InputSource::from_args(&args("01 --input -")).unwrap(),
+            InputSource::Stdin
+        );
+        assert!(InputSource::from_args(&args("01 --input")).is_err());
+        assert!(InputSource::from_args(&args("01 --input --time")).is_err());
+    }
+
+    #[test]
+    fn parses_example() {
+        let source = InputSource::from_args(&args("01 --example")).unwrap();
+        assert_eq!(source, InputSource::Example(None));
+        assert!(source
+            .path(day!(8))
+            .unwrap()
+            .ends_with("data/examples/08.txt"));
+
+        let source = InputSource::from_args(&args("01 --example 2 --time")).unwrap();
+        assert_eq!(source, InputSource::Example(Some(2)));
+        assert!(source
+            .path(day!(8))
+            .unwrap()
+            .ends_with("data/examples/08_2.txt"));
+
+        let source = InputSource::from_args(&args("01 --example --time")).unwrap();
+        assert_eq!(source, InputSource::Example(None));
+        assert!(InputSource::from_args(&args("01 --example x")).is_err());
+    }
+
+    #[test]
+    fn rejects_multiple_sources() {
+        assert!(InputSource::from_args(&args("01 --example --input foo.txt")).is_err());
+    }
+
+    #[test]
+    fn lists_path_in_error() {
+        let source = InputSource::Path("does/not/exist.txt".into());
+        let err = source.read(day!(1)).unwrap_err();
+        assert!(err.contains("\"does/not/exist.txt\""));
+    }
+}
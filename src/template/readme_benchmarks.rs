@@ -18,17 +18,21 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct Timings {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
+    pub part_1_memory: Option<String>,
+    pub part_2_memory: Option<String>,
     pub total_nanos: f64,
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
 }
 
 #[must_use]
@@ -36,12 +40,13 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locates the table enclosed by two occurrences of `marker`, or the position of a single one.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
+            "Too many occurrences of marker in README.".into(),
         ));
     }
 
@@ -58,38 +63,59 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+/// Builds a markdown benchmarking table with a heading of level `prefix`.
+#[must_use]
+pub fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // memory columns are only added if at least one day was measured with the `memory` feature.
+    let has_memory = timings
+        .iter()
+        .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some());
+
+    let mut lines: Vec<String> = vec![header, String::new()];
+
+    if has_memory {
+        lines.push("| Day | Part 1 | Part 2 | Part 1 Memory | Part 2 Memory |".into());
+        lines.push("| :---: | :---: | :---: | :---: | :---:  |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+        let mut line = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        );
+
+        if has_memory {
+            line.push_str(&format!(
+                " `{}` | `{}` |",
+                timing.part_1_memory.unwrap_or_else(|| "-".into()),
+                timing.part_2_memory.unwrap_or_else(|| "-".into())
+            ));
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
 fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let positions = locate_table(s, MARKER)?;
+    let table = format!(
+        "{MARKER}\n{}\n{MARKER}",
+        construct_table("##", timings, total_millis)
+    );
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
@@ -102,10 +128,11 @@ pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
     Ok(())
 }
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{locate_table, update_content, Error, Timings, MARKER};
+    use crate::{day, Day};
+    use proptest::prelude::*;
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
@@ -113,18 +140,30 @@ mod tests {
                 day: day!(1),
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
+                part_1_nanos: Some(1e+07),
+                part_2_nanos: Some(2e+07),
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
+                part_1_nanos: Some(3e+07),
+                part_2_nanos: Some(4e+07),
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
+                part_1_nanos: Some(4e+07),
+                part_2_nanos: Some(5e+07),
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 9e+10,
             },
         ]
@@ -148,7 +187,7 @@ mod tests {
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
         update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        assert!(s.contains("## Benchmarks"));
     }
 
     #[test]
@@ -183,4 +222,103 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings[0].part_1_memory = Some("1.5 KiB peak, 12 allocs".into());
+        timings[2].part_2_memory = Some("2.0 MiB peak, 3 allocs".into());
+
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        let expected = [
+            "foo",
+            "bar",
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Part 1 Memory | Part 2 Memory |",
+            "| :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `1.5 KiB peak, 12 allocs` | `-` |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` | `-` |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | `-` | `2.0 MiB peak, 3 allocs` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    fn timings_strategy() -> impl Strategy<Value = Vec<Timings>> {
+        let days = proptest::sample::subsequence((1..=25).collect::<Vec<u8>>(), 0..=25);
+        let part = proptest::option::of("[0-9]{1,3}\\.[0-9]{2}(ns|µs|ms|s)");
+        let memory = proptest::option::of("[0-9]{1,3}\\.[0-9] (B|KiB|MiB) peak, [0-9]{1,4} allocs");
+        days.prop_flat_map(move |days| {
+            let count = days.len();
+            (
+                Just(days),
+                proptest::collection::vec(
+                    (part.clone(), part.clone(), memory.clone(), memory.clone()),
+                    count,
+                ),
+            )
+        })
+        .prop_map(|(days, parts)| {
+            days.into_iter()
+                .zip(parts)
+                .map(
+                    |(day, (part_1, part_2, part_1_memory, part_2_memory))| Timings {
+                        day: Day::new(day).unwrap(),
+                        part_1,
+                        part_2,
+                        part_1_nanos: None,
+                        part_2_nanos: None,
+                        part_1_memory,
+                        part_2_memory,
+                        total_nanos: 0.0,
+                    },
+                )
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn update_is_idempotent(
+            before in "[a-z# \n]{0,40}",
+            after in "[a-z# \n]{0,40}",
+            timings in timings_strategy(),
+            total in 0.0_f64..1e6,
+        ) {
+            let mut s = format!("{before}{MARKER}\n{MARKER}{after}");
+            update_content(&mut s, timings.clone(), total).unwrap();
+            let once = s.clone();
+            update_content(&mut s, timings, total).unwrap();
+
+            prop_assert_eq!(&s, &once);
+            prop_assert!(s.starts_with(&before));
+            prop_assert!(s.ends_with(&after));
+            prop_assert_eq!(s.matches(MARKER).count(), 2);
+            prop_assert_eq!(s.matches("## Benchmarks").count(), 1);
+        }
+
+        #[test]
+        fn locates_table_by_marker_count(parts in proptest::collection::vec("[a-z \n]{0,10}", 1..6)) {
+            let s = parts.join(MARKER);
+            let markers = parts.len() - 1;
+            match (locate_table(&s, MARKER), markers) {
+                (Ok(position), 1 | 2) => {
+                    prop_assert_eq!(position.pos_start, parts[0].len());
+                    prop_assert_eq!(position.pos_end, s.len() - parts[markers].len());
+                }
+                (Err(Error::Parser(e)), 0) => prop_assert!(e.contains("Could not find table")),
+                (Err(Error::Parser(e)), _) if markers > 2 => {
+                    prop_assert_eq!(e, "Too many occurrences of marker in README.");
+                }
+                _ => prop_assert!(false, "unexpected result for {} markers", markers),
+            }
+        }
+    }
 }
@@ -0,0 +1,308 @@
+/// Module that updates the readme with a table of collected stars, computed from local state
+/// instead of the Advent of Code API like the `advent-readme-stars` action does.
+use std::fs;
+
+use crate::template::readme_benchmarks::{Error, TablePosition};
+use crate::Day;
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+static YEAR_PLACEHOLDER: &str = "{year}";
+
+/// Status of a part's answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Star {
+    /// The answer matches the one accepted by Advent of Code.
+    Verified,
+    /// The answer differs from the one accepted by Advent of Code.
+    Wrong,
+    /// The part returns an answer, but no accepted answer is stored locally.
+    Unverified,
+    /// The part returns `None` or fails.
+    Unsolved,
+}
+
+impl Star {
+    /// Compares an answer with the accepted one from the puzzle description.
+    #[must_use]
+    pub fn new(answer: Option<&str>, accepted: Option<&str>) -> Self {
+        match (answer, accepted) {
+            (None, _) => Star::Unsolved,
+            (Some(_), None) => Star::Unverified,
+            (Some(answer), Some(accepted)) if answer == accepted => Star::Verified,
+            (Some(_), Some(_)) => Star::Wrong,
+        }
+    }
+
+    #[must_use]
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Star::Verified => "⭐",
+            Star::Wrong => "❌",
+            Star::Unverified => "❔",
+            Star::Unsolved => "✖",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Progress {
+    pub day: Day,
+    pub part_1: Star,
+    pub part_2: Star,
+}
+
+/// Extracts the accepted answers from a puzzle description downloaded by aoc-cli, which lists
+/// them as "Your puzzle answer was `42`." once a part is solved.
+#[must_use]
+pub fn parse_accepted_answers(puzzle: &str) -> Vec<String> {
+    puzzle
+        .split("Your puzzle answer was")
+        .skip(1)
+        .filter_map(|rest| {
+            let rest = rest.trim_start().strip_prefix('`')?;
+            let (answer, _) = rest.split_once('`')?;
+            Some(answer.to_string())
+        })
+        .collect()
+}
+
+/// Builds a markdown table with one row per scaffolded day, with a heading of level `prefix`.
+#[must_use]
+pub fn construct_table(prefix: &str, year: u16, progress: &[Progress]) -> String {
+    let stars = progress
+        .iter()
+        .flat_map(|p| [p.part_1, p.part_2])
+        .filter(|star| *star == Star::Verified)
+        .count();
+
+    let mut lines: Vec<String> = vec![
+        format!("{prefix} {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for p in progress {
+        let day = p.day.into_inner();
+        lines.push(format!(
+            "| [Day {day}](https://adventofcode.com/{year}/day/{day}) | {} | {} |",
+            p.part_1.symbol(),
+            p.part_2.symbol()
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "**Stars: {stars}/50** ({} verified, {} not verified yet, {} wrong, {} unsolved)",
+        Star::Verified.symbol(),
+        Star::Unverified.symbol(),
+        Star::Wrong.symbol(),
+        Star::Unsolved.symbol()
+    ));
+
+    lines.join("\n")
+}
+
+/// Replaces `{year}` in the title of the readme, other occurrences are left as is.
+fn fill_year(s: &mut String, year: u16) {
+    let title = s
+        .split_inclusive('\n')
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some((start, line))
+        })
+        .find(|(_, line)| line.starts_with("# "));
+
+    if let Some((start, line)) = title {
+        let end = start + line.len();
+        let filled = line.replace(YEAR_PLACEHOLDER, &year.to_string());
+        s.replace_range(start..end, &filled);
+    }
+}
+
+/// Locates the table enclosed by two marker lines, or the position of a single one.
+/// Markers quoted within a line, e.g. in the documentation of the readme, are not matched.
+fn locate_table(readme: &str) -> Result<TablePosition, Error> {
+    let markers: Vec<usize> = readme
+        .split_inclusive('\n')
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some((start, line))
+        })
+        .filter(|(_, line)| line.trim_end() == MARKER)
+        .map(|(start, _)| start)
+        .collect();
+
+    let (pos_start, pos_end) = match markers[..] {
+        [] => return Err(Error::Parser("Could not find table start position.".into())),
+        [start] => (start, start + MARKER.len()),
+        [start, end] => {
+            // refuse to overwrite anything but a table written by a previous run.
+            if !is_progress_table(readme[start + MARKER.len()..end].trim()) {
+                return Err(Error::Parser(
+                    "Text between the markers is not a progress table.".into(),
+                ));
+            }
+            (start, end + MARKER.len())
+        }
+        _ => {
+            return Err(Error::Parser(
+                "Too many occurrences of marker in README.".into(),
+            ))
+        }
+    };
+
+    Ok(TablePosition { pos_start, pos_end })
+}
+
+/// Whether `text` is empty or a table built by [`construct_table`].
+fn is_progress_table(text: &str) -> bool {
+    let (Some(first), Some(last)) = (text.lines().next(), text.lines().last()) else {
+        return true;
+    };
+    first.starts_with("## ") && first.ends_with(" Results") && last.starts_with("**Stars: ")
+}
+
+fn update_content(s: &mut String, year: u16, progress: &[Progress]) -> Result<(), Error> {
+    let positions = locate_table(s)?;
+    let table = format!(
+        "{MARKER}\n{}\n{MARKER}",
+        construct_table("##", year, progress)
+    );
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    fill_year(s, year);
+    Ok(())
+}
+
+pub fn update(year: u16, progress: &[Progress]) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, progress)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{construct_table, parse_accepted_answers, update_content, Progress, Star, MARKER};
+    use crate::day;
+
+    fn get_mock_progress() -> Vec<Progress> {
+        vec![
+            Progress {
+                day: day!(1),
+                part_1: Star::Verified,
+                part_2: Star::Verified,
+            },
+            Progress {
+                day: day!(20),
+                part_1: Star::Unverified,
+                part_2: Star::Unsolved,
+            },
+            Progress {
+                day: day!(24),
+                part_1: Star::Wrong,
+                part_2: Star::Unsolved,
+            },
+        ]
+    }
+
+    #[test]
+    fn rates_answers() {
+        assert_eq!(Star::new(Some("42"), Some("42")), Star::Verified);
+        assert_eq!(Star::new(Some("41"), Some("42")), Star::Wrong);
+        assert_eq!(Star::new(Some("42"), None), Star::Unverified);
+        assert_eq!(Star::new(None, Some("42")), Star::Unsolved);
+        assert_eq!(Star::new(None, None), Star::Unsolved);
+    }
+
+    #[test]
+    fn parses_accepted_answers() {
+        let puzzle = "\\--- Day 1: Trebuchet?! ---\n\nYour puzzle answer was `54697`.\n\n\
+                      \\--- Part Two ---\n\nYour puzzle answer was `54885`.\n\nBoth parts of this puzzle are complete!";
+        assert_eq!(parse_accepted_answers(puzzle), vec!["54697", "54885"]);
+        assert_eq!(
+            parse_accepted_answers("To begin, get your puzzle input."),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, 2023, &get_mock_progress()).unwrap();
+    }
+
+    #[test]
+    fn format_progress() {
+        let mut s = format!("# 🎄 Advent of Code {{year}}\n\nfoo\n{MARKER}\n\nsee https://adventofcode.com/{{year}}/");
+        update_content(&mut s, 2023, &get_mock_progress()).unwrap();
+        let expected = [
+            "# 🎄 Advent of Code 2023",
+            "",
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 20](https://adventofcode.com/2023/day/20) | ❔ | ✖ |",
+            "| [Day 24](https://adventofcode.com/2023/day/24) | ❌ | ✖ |",
+            "",
+            "**Stars: 2/50** (⭐ verified, ❔ not verified yet, ❌ wrong, ✖ unsolved)",
+            "<!--- advent_readme_stars table --->",
+            "",
+            "see https://adventofcode.com/{year}/",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_existing_progress() {
+        let mut s = format!("# Advent of Code {{year}}\n{MARKER}\nbaz");
+        update_content(&mut s, 2023, &get_mock_progress()).unwrap();
+        let once = s.clone();
+        update_content(&mut s, 2023, &get_mock_progress()).unwrap();
+        assert_eq!(s, once);
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## 2023 Results").count(), 1);
+    }
+
+    #[test]
+    fn ignores_quoted_markers() {
+        let mut s = format!("# readme\n{MARKER}\n\n## Usage\n\nreplaces the `{MARKER}` marker.\n");
+        update_content(&mut s, 2023, &get_mock_progress()).unwrap();
+        assert!(s.ends_with(&format!(
+            "\n\n## Usage\n\nreplaces the `{MARKER}` marker.\n"
+        )));
+        assert_eq!(s.matches("## 2023 Results").count(), 1);
+
+        // text between two markers is never replaced, unless it is a progress table.
+        let mut s = format!("# readme\n{MARKER}\n\n## Usage\n\n{MARKER}\n");
+        assert!(update_content(&mut s, 2023, &get_mock_progress()).is_err());
+    }
+
+    #[test]
+    fn updates_readme() {
+        let readme = include_str!("../../README.md");
+        let mut s = readme.to_string();
+        update_content(&mut s, 2023, &get_mock_progress()).unwrap();
+
+        let table = construct_table("##", 2023, &get_mock_progress());
+        let expected = readme.replacen("{year}", "2023", 1).replacen(
+            &format!("{MARKER}\n"),
+            &format!("{MARKER}\n{table}\n{MARKER}\n"),
+            1,
+        );
+        assert_eq!(s, expected);
+
+        let once = s.clone();
+        update_content(&mut s, 2023, &get_mock_progress()).unwrap();
+        assert_eq!(s, once);
+    }
+}
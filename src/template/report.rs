@@ -0,0 +1,395 @@
+/// Module that writes benchmark results to standalone report files.
+/// In contrast to `readme_benchmarks`, the README is not touched.
+use std::{fmt::Display, fs, str::FromStr};
+
+use crate::template::readme_benchmarks::{self, Error, Timings};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+    Markdown,
+    Svg,
+}
+
+impl ReportFormat {
+    /// Default file extension for the format.
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Csv => "csv",
+            ReportFormat::Json => "json",
+            ReportFormat::Markdown => "md",
+            ReportFormat::Svg => "svg",
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = ReportFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ReportFormat::Csv),
+            "json" => Ok(ReportFormat::Json),
+            "md" | "markdown" => Ok(ReportFormat::Markdown),
+            "svg" => Ok(ReportFormat::Svg),
+            _ => Err(ReportFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`ReportFormat`].
+#[derive(Debug)]
+pub struct ReportFormatFromStrError;
+
+impl std::error::Error for ReportFormatFromStrError {}
+
+impl Display for ReportFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of the report formats: csv, json, markdown, svg")
+    }
+}
+
+#[must_use]
+pub fn render(format: ReportFormat, timings: Vec<Timings>, total_millis: f64) -> String {
+    match format {
+        ReportFormat::Csv => render_csv(&timings),
+        ReportFormat::Json => render_json(&timings, total_millis),
+        ReportFormat::Markdown => {
+            let mut table = readme_benchmarks::construct_table("#", timings, total_millis);
+            table.push('\n');
+            table
+        }
+        ReportFormat::Svg => render_svg(&timings),
+    }
+}
+
+pub fn write(
+    format: ReportFormat,
+    path: &str,
+    timings: Vec<Timings>,
+    total_millis: f64,
+) -> Result<(), Error> {
+    fs::write(path, render(format, timings, total_millis))?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn csv_field(value: Option<&str>) -> String {
+    match value {
+        Some(s) if s.contains([',', '"', '\n']) => format!("\"{}\"", s.replace('"', "\"\"")),
+        Some(s) => s.to_string(),
+        None => String::new(),
+    }
+}
+
+fn render_csv(timings: &[Timings]) -> String {
+    let mut lines: Vec<String> =
+        vec!["day,part_1,part_1_nanos,part_1_memory,part_2,part_2_nanos,part_2_memory".into()];
+
+    for timing in timings {
+        lines.push(format!(
+            "{},{},{},{},{},{},{}",
+            timing.day.into_inner(),
+            csv_field(timing.part_1.as_deref()),
+            csv_field(timing.part_1_nanos.map(|n| n.to_string()).as_deref()),
+            csv_field(timing.part_1_memory.as_deref()),
+            csv_field(timing.part_2.as_deref()),
+            csv_field(timing.part_2_nanos.map(|n| n.to_string()).as_deref()),
+            csv_field(timing.part_2_memory.as_deref()),
+        ));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn json_string(value: Option<&str>) -> String {
+    let Some(s) = value else {
+        return "null".into();
+    };
+
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_part(time: Option<&str>, nanos: Option<f64>, memory: Option<&str>) -> String {
+    match (time, nanos) {
+        (Some(time), Some(nanos)) => format!(
+            "{{ \"time\": {}, \"nanos\": {nanos}, \"memory\": {} }}",
+            json_string(Some(time)),
+            json_string(memory)
+        ),
+        _ => "null".into(),
+    }
+}
+
+fn render_json(timings: &[Timings], total_millis: f64) -> String {
+    let days: Vec<String> = timings
+        .iter()
+        .map(|timing| {
+            format!(
+                "    {{ \"day\": {}, \"part_1\": {}, \"part_2\": {}, \"total_nanos\": {} }}",
+                timing.day.into_inner(),
+                json_part(
+                    timing.part_1.as_deref(),
+                    timing.part_1_nanos,
+                    timing.part_1_memory.as_deref()
+                ),
+                json_part(
+                    timing.part_2.as_deref(),
+                    timing.part_2_nanos,
+                    timing.part_2_memory.as_deref()
+                ),
+                timing.total_nanos
+            )
+        })
+        .collect();
+
+    let days = if days.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n  ]", days.join(",\n"))
+    };
+
+    format!("{{\n  \"days\": {days},\n  \"total_millis\": {total_millis}\n}}\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+const SVG_WIDTH: f64 = 800.0;
+const SVG_LABEL_WIDTH: f64 = 70.0;
+const SVG_MARGIN: f64 = 20.0;
+const SVG_HEADER_HEIGHT: f64 = 50.0;
+const SVG_FOOTER_HEIGHT: f64 = 30.0;
+const SVG_BAR_HEIGHT: f64 = 10.0;
+const SVG_ROW_HEIGHT: f64 = 28.0;
+const SVG_COLOR_PART_1: &str = "#4e79a7";
+const SVG_COLOR_PART_2: &str = "#f28e2b";
+
+fn format_nanos(nanos: f64) -> String {
+    match nanos {
+        n if n >= 1e9 => format!("{}s", n / 1e9),
+        n if n >= 1e6 => format!("{}ms", n / 1e6),
+        n if n >= 1e3 => format!("{}µs", n / 1e3),
+        n => format!("{n}ns"),
+    }
+}
+
+/// Renders a horizontal bar chart of the part timings with a logarithmic time axis.
+fn render_svg(timings: &[Timings]) -> String {
+    let all_nanos: Vec<f64> = timings
+        .iter()
+        .flat_map(|t| [t.part_1_nanos, t.part_2_nanos])
+        .flatten()
+        .filter(|n| *n > 0.0)
+        .collect();
+
+    // the axis spans whole powers of ten so that tick labels are round numbers.
+    let min_exp = all_nanos
+        .iter()
+        .fold(f64::INFINITY, |acc, n| acc.min(n.log10().floor()));
+    let max_exp = all_nanos
+        .iter()
+        .fold(f64::NEG_INFINITY, |acc, n| acc.max(n.log10().ceil()));
+    let (min_exp, max_exp) = if all_nanos.is_empty() {
+        (0.0, 1.0)
+    } else if min_exp == max_exp {
+        (min_exp, max_exp + 1.0)
+    } else {
+        (min_exp, max_exp)
+    };
+
+    let chart_left = SVG_MARGIN + SVG_LABEL_WIDTH;
+    let chart_width = SVG_WIDTH - chart_left - SVG_MARGIN;
+    #[allow(clippy::cast_precision_loss)]
+    let chart_height = SVG_ROW_HEIGHT * timings.len() as f64;
+    let chart_bottom = SVG_HEADER_HEIGHT + chart_height;
+    let height = chart_bottom + SVG_FOOTER_HEIGHT;
+
+    let x_for = |nanos: f64| {
+        let exp = nanos.max(1.0).log10().clamp(min_exp, max_exp);
+        chart_left + (exp - min_exp) / (max_exp - min_exp) * chart_width
+    };
+
+    let mut elements: Vec<String> = vec![
+        format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{SVG_WIDTH}\" height=\"{height}\" viewBox=\"0 0 {SVG_WIDTH} {height}\" font-family=\"sans-serif\" font-size=\"12\">"),
+        format!("  <rect width=\"{SVG_WIDTH}\" height=\"{height}\" fill=\"#ffffff\"/>"),
+        format!("  <text x=\"{SVG_MARGIN}\" y=\"24\" font-size=\"16\" font-weight=\"bold\">Benchmarks</text>"),
+        format!("  <rect x=\"{}\" y=\"14\" width=\"10\" height=\"10\" fill=\"{SVG_COLOR_PART_1}\"/>", SVG_WIDTH - 180.0),
+        format!("  <text x=\"{}\" y=\"23\">Part 1</text>", SVG_WIDTH - 165.0),
+        format!("  <rect x=\"{}\" y=\"14\" width=\"10\" height=\"10\" fill=\"{SVG_COLOR_PART_2}\"/>", SVG_WIDTH - 100.0),
+        format!("  <text x=\"{}\" y=\"23\">Part 2</text>", SVG_WIDTH - 85.0),
+    ];
+
+    #[allow(clippy::cast_possible_truncation)]
+    for exp in (min_exp as i32)..=(max_exp as i32) {
+        let x = x_for(10_f64.powi(exp));
+        elements.push(format!(
+            "  <line x1=\"{x:.2}\" y1=\"{SVG_HEADER_HEIGHT}\" x2=\"{x:.2}\" y2=\"{chart_bottom}\" stroke=\"#dddddd\"/>"
+        ));
+        elements.push(format!(
+            "  <text x=\"{x:.2}\" y=\"{}\" text-anchor=\"middle\" fill=\"#666666\">{}</text>",
+            chart_bottom + 18.0,
+            format_nanos(10_f64.powi(exp))
+        ));
+    }
+
+    for (i, timing) in timings.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let row_top = SVG_HEADER_HEIGHT + SVG_ROW_HEIGHT * i as f64;
+
+        elements.push(format!(
+            "  <text x=\"{SVG_MARGIN}\" y=\"{}\">Day {}</text>",
+            row_top + SVG_ROW_HEIGHT / 2.0 + 4.0,
+            timing.day.into_inner()
+        ));
+
+        let parts = [
+            (
+                timing.part_1_nanos,
+                timing.part_1.as_deref(),
+                SVG_COLOR_PART_1,
+            ),
+            (
+                timing.part_2_nanos,
+                timing.part_2.as_deref(),
+                SVG_COLOR_PART_2,
+            ),
+        ];
+
+        for (j, (nanos, label, color)) in parts.into_iter().enumerate() {
+            let (Some(nanos), Some(label)) = (nanos, label) else {
+                continue;
+            };
+            #[allow(clippy::cast_precision_loss)]
+            let y = row_top + 4.0 + (SVG_BAR_HEIGHT + 1.0) * j as f64;
+            let width = (x_for(nanos) - chart_left).max(1.0);
+            elements.push(format!(
+                "  <rect x=\"{chart_left}\" y=\"{y}\" width=\"{width:.2}\" height=\"{SVG_BAR_HEIGHT}\" fill=\"{color}\"><title>Day {} Part {}: {label}</title></rect>",
+                timing.day.into_inner(),
+                j + 1,
+            ));
+        }
+    }
+
+    elements.push("</svg>".into());
+    elements.push(String::new());
+    elements.join("\n")
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{render, ReportFormat};
+    use crate::{day, template::readme_benchmarks::Timings};
+
+    fn get_mock_timings() -> Vec<Timings> {
+        vec![
+            Timings {
+                day: day!(1),
+                part_1: Some("10ms".into()),
+                part_2: Some("20µs".into()),
+                part_1_nanos: Some(1e+07),
+                part_2_nanos: Some(2e+04),
+                part_1_memory: Some("1.5 KiB peak, 12 allocs".into()),
+                part_2_memory: None,
+                total_nanos: 1.002e+07,
+            },
+            Timings {
+                day: day!(3),
+                part_1: Some("30ns".into()),
+                part_2: None,
+                part_1_nanos: Some(30.0),
+                part_2_nanos: None,
+                part_1_memory: None,
+                part_2_memory: None,
+                total_nanos: 30.0,
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("csv".parse::<ReportFormat>().unwrap(), ReportFormat::Csv);
+        assert_eq!("JSON".parse::<ReportFormat>().unwrap(), ReportFormat::Json);
+        assert_eq!(
+            "md".parse::<ReportFormat>().unwrap(),
+            ReportFormat::Markdown
+        );
+        assert_eq!(
+            "markdown".parse::<ReportFormat>().unwrap(),
+            ReportFormat::Markdown
+        );
+        assert_eq!("svg".parse::<ReportFormat>().unwrap(), ReportFormat::Svg);
+        assert!("html".parse::<ReportFormat>().is_err());
+    }
+
+    #[test]
+    fn renders_csv() {
+        let expected = [
+            "day,part_1,part_1_nanos,part_1_memory,part_2,part_2_nanos,part_2_memory",
+            "1,10ms,10000000,\"1.5 KiB peak, 12 allocs\",20µs,20000,",
+            "3,30ns,30,,,,",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(
+            render(ReportFormat::Csv, get_mock_timings(), 10.02),
+            expected
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        let expected = [
+            "{",
+            "  \"days\": [",
+            "    { \"day\": 1, \"part_1\": { \"time\": \"10ms\", \"nanos\": 10000000, \"memory\": \"1.5 KiB peak, 12 allocs\" }, \"part_2\": { \"time\": \"20µs\", \"nanos\": 20000, \"memory\": null }, \"total_nanos\": 10020000 },",
+            "    { \"day\": 3, \"part_1\": { \"time\": \"30ns\", \"nanos\": 30, \"memory\": null }, \"part_2\": null, \"total_nanos\": 30 }",
+            "  ],",
+            "  \"total_millis\": 10.02",
+            "}",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(
+            render(ReportFormat::Json, get_mock_timings(), 10.02),
+            expected
+        );
+    }
+
+    #[test]
+    fn renders_markdown_without_markers() {
+        let s = render(ReportFormat::Markdown, get_mock_timings(), 10.02);
+        assert!(s.starts_with("# Benchmarks\n"));
+        assert!(!s.contains("<!---"));
+        assert!(s.contains("| [Day 3](./src/bin/03.rs) | `30ns` | `-` |"));
+    }
+
+    #[test]
+    fn renders_svg() {
+        let s = render(ReportFormat::Svg, get_mock_timings(), 10.02);
+        assert!(s.starts_with("<svg "));
+        assert!(s.trim_end().ends_with("</svg>"));
+        // one bar per measured part.
+        assert_eq!(s.matches("<title>").count(), 3);
+        // log axis spans 10ns to 10ms.
+        assert!(s.contains(">10ns</text>"));
+        assert!(s.contains(">10ms</text>"));
+    }
+}
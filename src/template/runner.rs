@@ -1,38 +1,56 @@
 /// Encapsulates code that interacts with solution functions.
+use crate::template::memory::MemoryUsage;
+use crate::template::stats::{self, Estimate};
 use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::validate::Validation;
+use crate::{Day, PartAnswer};
 use std::fmt::Display;
 use std::io::{stdout, Write};
 use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, T: PartAnswer>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
+    let (result, duration, samples, memory) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
+    if let Some(memory) = memory {
+        println!("{}", memory.record(part));
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    // answers that overflowed are never submitted.
+    if let Some(Ok(answer)) = result.as_ref().map(PartAnswer::answer) {
+        submit_result(answer, day, part);
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Heap usage is measured on the first execution if the `memory` feature is enabled.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+) -> (T, Duration, u128, Option<MemoryUsage>) {
+    let cloned = input.clone();
+    let ((result, base_time), memory) = run_measured(|| {
+        let timer = Instant::now();
+        let result = func(cloned);
+        (result, timer.elapsed())
+    });
 
     hook(&result);
 
@@ -42,7 +60,34 @@ fn run_timed<I: Clone, T>(
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, memory)
+}
+
+#[cfg(feature = "memory")]
+fn run_measured<T>(func: impl FnOnce() -> T) -> (T, Option<MemoryUsage>) {
+    let (result, usage) = crate::template::memory::measure(func);
+    (result, Some(usage))
+}
+
+#[cfg(not(feature = "memory"))]
+fn run_measured<T>(func: impl FnOnce() -> T) -> (T, Option<MemoryUsage>) {
+    (func(), None)
+}
+
+static BENCHING: AtomicBool = AtomicBool::new(false);
+
+/// Whether a part is currently re-run for benching. Visualization and graph export skip these
+/// runs, so they happen once per part and are not part of the measured time.
+#[must_use]
+pub fn is_benching() -> bool {
+    BENCHING.load(Ordering::Relaxed)
+}
+
+fn benching<T>(run: impl FnOnce() -> T) -> T {
+    BENCHING.store(true, Ordering::Relaxed);
+    let result = run();
+    BENCHING.store(false, Ordering::Relaxed);
+    result
 }
 
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
@@ -51,23 +96,20 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
-    for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
-        let timer = Instant::now();
-        func(cloned);
-        timers.push(timer.elapsed());
-    }
+    benching(|| {
+        for _ in 0..bench_iterations {
+            // need a clone here to make the borrow checker happy.
+            let cloned = input.clone();
+            let timer = Instant::now();
+            func(cloned);
+            timers.push(timer.elapsed());
+        }
+    });
 
     (
         #[allow(clippy::cast_possible_truncation)]
@@ -92,12 +134,156 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Runs the input validation of a day, if one is registered.
+/// With `--check-input`, the result is reported and the process exits. Otherwise, violations are
+/// printed as a warning before solving.
+pub fn validate_input(validate: Option<fn(&str) -> Validation>, input: &str) {
+    let is_check = env::args().any(|x| x == "--check-input");
+
+    match validate.map(|validate| validate(input)) {
+        Some(Err(violations)) => {
+            eprintln!("{ANSI_BOLD}Input violates assumptions:{ANSI_RESET}");
+            for violation in violations {
+                eprintln!("  ✖ {violation}");
+            }
+            if is_check {
+                process::exit(1);
+            }
+        }
+        Some(Ok(())) if is_check => {
+            println!("Input OK ✔");
+            process::exit(0);
+        }
+        None if is_check => {
+            println!("No input assumptions declared.");
+            process::exit(0);
+        }
+        _ => {}
+    }
+}
+
+/// Returns the part requested via `--compare [part]`.
+/// The outer option is `None` if no comparison was requested, the inner one if all parts should be compared.
+#[must_use]
+pub fn compare_arg() -> Option<Option<u8>> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--compare")?;
+    Some(args.get(index + 1).and_then(|x| x.parse().ok()))
+}
+
+/// Benches two implementations of the same part against each other on the same input.
+/// Both functions are first checked for identical answers. Samples are interleaved so that
+/// noise (e.g. thermal throttling) affects both implementations equally.
+pub fn compare_parts<I: Clone, T: PartAnswer + PartialEq>(
+    baseline: (&str, impl Fn(I) -> Option<T>),
+    candidate: (&str, impl Fn(I) -> Option<T>),
+    input: I,
+    part: u8,
+) {
+    let (baseline_name, baseline_func) = baseline;
+    let (candidate_name, candidate_func) = candidate;
+
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}: {baseline_name} vs. {candidate_name}");
+
+    let timer = Instant::now();
+    let baseline_result = baseline_func(input.clone());
+    let baseline_time = timer.elapsed();
+
+    let timer = Instant::now();
+    let candidate_result = candidate_func(input.clone());
+    let candidate_time = timer.elapsed();
+
+    if baseline_result != candidate_result {
+        let format_result = |result: &Option<T>| match result.as_ref().map(PartAnswer::answer) {
+            Some(Ok(answer)) => answer,
+            Some(Err(e)) => format!("✖ ({e})"),
+            None => "✖".into(),
+        };
+        eprintln!(
+            "Answers differ: {baseline_name} returned {}, {candidate_name} returned {}.",
+            format_result(&baseline_result),
+            format_result(&candidate_result)
+        );
+        process::exit(1);
+    }
+
+    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    let _ = stdout().flush();
+
+    let bench_iterations = (Duration::from_secs(2).as_nanos()
+        / cmp::max(baseline_time.as_nanos() + candidate_time.as_nanos(), 10))
+    .clamp(10, 10000);
+
+    let mut baseline_timers: Vec<Duration> = vec![];
+    let mut candidate_timers: Vec<Duration> = vec![];
+
+    benching(|| {
+        for _ in 0..bench_iterations {
+            let cloned = input.clone();
+            let timer = Instant::now();
+            baseline_func(cloned);
+            baseline_timers.push(timer.elapsed());
+
+            let cloned = input.clone();
+            let timer = Instant::now();
+            candidate_func(cloned);
+            candidate_timers.push(timer.elapsed());
+        }
+    });
+
+    let comparison = stats::compare(
+        &stats::to_nanos(&baseline_timers),
+        &stats::to_nanos(&candidate_timers),
+    );
+
+    print!("\r");
+    let width = cmp::max(baseline_name.len(), candidate_name.len());
+    println!(
+        "  {baseline_name:width$}: {} @ {bench_iterations} samples",
+        format_estimate(&comparison.baseline)
+    );
+    println!(
+        "  {candidate_name:width$}: {} @ {bench_iterations} samples",
+        format_estimate(&comparison.candidate)
+    );
+
+    let speedup = comparison.speedup;
+    let verdict = if speedup.lower > 1.0 {
+        format!(
+            "{candidate_name} is {ANSI_BOLD}{:.2}x faster{ANSI_RESET}",
+            speedup.point
+        )
+    } else if speedup.upper < 1.0 {
+        format!(
+            "{candidate_name} is {ANSI_BOLD}{:.2}x slower{ANSI_RESET}",
+            1.0 / speedup.point
+        )
+    } else {
+        "no significant difference".into()
+    };
+    println!(
+        "  {verdict} (speedup {:.2}x, 95% CI [{:.2}x, {:.2}x])",
+        speedup.point, speedup.lower, speedup.upper
+    );
+}
+
+fn format_estimate(estimate: &Estimate) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let to_duration = |nanos: f64| Duration::from_nanos(nanos as u64);
+    format!(
+        "{:.1?} (95% CI [{:.1?}, {:.1?}])",
+        to_duration(estimate.point),
+        to_duration(estimate.lower),
+        to_duration(estimate.upper)
+    )
+}
+
+fn print_result<T: PartAnswer>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+    match result.as_ref().map(PartAnswer::answer) {
+        Some(Ok(result)) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -116,6 +302,14 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
+        Some(Err(e)) => {
+            if is_intermediate_result {
+                print!("{part}: ✖ {e}");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ {e}             ");
+            }
+        }
         None => {
             if is_intermediate_result {
                 print!("{part}: ✖");
@@ -0,0 +1,126 @@
+/// Statistics helpers used to compare the run times of two solution variants.
+/// The approach is similar to how `criterion` reports comparisons: sample means with
+/// bootstrapped confidence intervals.
+use std::time::Duration;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const BOOTSTRAP_RESAMPLES: usize = 1000;
+const CONFIDENCE_LEVEL: f64 = 0.95;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub point: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    /// Mean run time of the baseline in nanoseconds.
+    pub baseline: Estimate,
+    /// Mean run time of the candidate in nanoseconds.
+    pub candidate: Estimate,
+    /// How many times faster the candidate is than the baseline, i.e. `baseline / candidate`.
+    pub speedup: Estimate,
+}
+
+#[must_use]
+pub fn to_nanos(samples: &[Duration]) -> Vec<f64> {
+    #[allow(clippy::cast_precision_loss)]
+    samples.iter().map(|d| d.as_nanos() as f64).collect()
+}
+
+#[must_use]
+pub fn mean(samples: &[f64]) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let len = samples.len() as f64;
+    samples.iter().sum::<f64>() / len
+}
+
+/// Returns the value at `quantile` (0.0 to 1.0) of the sorted samples.
+fn percentile(sorted: &[f64], quantile: f64) -> f64 {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let index = ((sorted.len() - 1) as f64 * quantile).round() as usize;
+    sorted[index]
+}
+
+fn resample(rng: &mut StdRng, samples: &[f64]) -> Vec<f64> {
+    (0..samples.len())
+        .map(|_| samples[rng.gen_range(0..samples.len())])
+        .collect()
+}
+
+fn estimate(point: f64, mut bootstrapped: Vec<f64>) -> Estimate {
+    bootstrapped.sort_by(f64::total_cmp);
+    let alpha = (1.0 - CONFIDENCE_LEVEL) / 2.0;
+    Estimate {
+        point,
+        lower: percentile(&bootstrapped, alpha),
+        upper: percentile(&bootstrapped, 1.0 - alpha),
+    }
+}
+
+/// Compares two sets of run time samples (in nanoseconds).
+/// Confidence intervals are computed by bootstrapping both sample sets independently.
+#[must_use]
+pub fn compare(baseline: &[f64], candidate: &[f64]) -> Comparison {
+    // a fixed seed keeps the reported intervals stable for identical samples.
+    let mut rng = StdRng::seed_from_u64(0x00ad_0e27);
+
+    let mut baseline_means = Vec::with_capacity(BOOTSTRAP_RESAMPLES);
+    let mut candidate_means = Vec::with_capacity(BOOTSTRAP_RESAMPLES);
+    let mut speedups = Vec::with_capacity(BOOTSTRAP_RESAMPLES);
+
+    for _ in 0..BOOTSTRAP_RESAMPLES {
+        let baseline_mean = mean(&resample(&mut rng, baseline));
+        let candidate_mean = mean(&resample(&mut rng, candidate));
+        baseline_means.push(baseline_mean);
+        candidate_means.push(candidate_mean);
+        speedups.push(baseline_mean / candidate_mean);
+    }
+
+    let baseline_mean = mean(baseline);
+    let candidate_mean = mean(candidate);
+
+    Comparison {
+        baseline: estimate(baseline_mean, baseline_means),
+        candidate: estimate(candidate_mean, candidate_means),
+        speedup: estimate(baseline_mean / candidate_mean, speedups),
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{compare, mean};
+
+    #[test]
+    fn computes_mean() {
+        assert_eq!(mean(&[1.0, 2.0, 3.0, 6.0]), 3.0);
+    }
+
+    #[test]
+    fn identical_samples_have_no_speedup() {
+        let samples = vec![100.0, 110.0, 90.0, 105.0, 95.0];
+        let comparison = compare(&samples, &samples);
+        assert_eq!(comparison.speedup.point, 1.0);
+        assert_eq!(comparison.baseline.point, comparison.candidate.point);
+        // both sides are resampled independently, so only the interval is expected to cover 1.0.
+        assert!(comparison.speedup.lower <= 1.0 && comparison.speedup.upper >= 1.0);
+    }
+
+    #[test]
+    fn detects_faster_candidate() {
+        let baseline: Vec<f64> = (0..50).map(|i| 200.0 + f64::from(i % 5)).collect();
+        let candidate: Vec<f64> = (0..50).map(|i| 100.0 + f64::from(i % 5)).collect();
+        let comparison = compare(&baseline, &candidate);
+        assert!(comparison.speedup.point > 1.9 && comparison.speedup.point < 2.1);
+        assert!(comparison.speedup.lower > 1.0);
+        assert!(comparison.baseline.lower <= comparison.baseline.point);
+        assert!(comparison.baseline.upper >= comparison.baseline.point);
+    }
+}
@@ -0,0 +1,162 @@
+//! Helpers to declare the assumptions a solution makes about its input.
+//! Days register a validation function via `solution!(DAY, validate: validate)`, which is run by
+//! `cargo check-input` and before solving.
+
+/// Result of a validation, holding all violated assumptions on error.
+pub type Validation = Result<(), Vec<String>>;
+
+/// Collects violated input assumptions.
+#[derive(Debug, Default)]
+pub struct Validator {
+    violations: Vec<String>,
+}
+
+impl Validator {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `message` as a violation if `condition` does not hold.
+    pub fn check(&mut self, condition: bool, message: impl Into<String>) -> &mut Self {
+        if !condition {
+            self.violations.push(message.into());
+        }
+        self
+    }
+
+    /// Records the error of `result` as a violation and returns its value.
+    pub fn require<T>(&mut self, result: Result<T, String>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(violation) => {
+                self.violations.push(violation);
+                None
+            }
+        }
+    }
+
+    pub fn finish(self) -> Validation {
+        if self.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(self.violations)
+        }
+    }
+}
+
+/// Checks that the input is a non-empty grid with lines of equal length and returns its
+/// dimensions as `(rows, columns)`.
+pub fn rectangular_grid(input: &str) -> Result<(usize, usize), String> {
+    let mut lines = input.lines();
+    let width = lines
+        .next()
+        .map(|line| line.chars().count())
+        .filter(|width| *width > 0)
+        .ok_or_else(|| String::from("input is not a grid: first line is empty"))?;
+
+    let mut rows = 1;
+    for (i, line) in lines.enumerate() {
+        let line_width = line.chars().count();
+        if line_width != width {
+            return Err(format!(
+                "input is not a rectangular grid: line {} has {line_width} columns, expected {width}",
+                i + 2
+            ));
+        }
+        rows += 1;
+    }
+
+    Ok((rows, width))
+}
+
+/// Checks that the grid only consists of characters in `allowed`.
+pub fn grid_chars(input: &str, allowed: &str) -> Result<(), String> {
+    for (row, line) in input.lines().enumerate() {
+        if let Some((col, c)) = line
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !allowed.contains(*c))
+        {
+            return Err(format!(
+                "unexpected character '{c}' at row {row}, column {col}, expected one of \"{allowed}\""
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the `(row, column)` positions of all occurrences of `needle` in a grid.
+#[must_use]
+pub fn find_all(input: &str, needle: char) -> Vec<(usize, usize)> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .filter(move |(_, c)| *c == needle)
+                .map(move |(col, _)| (row, col))
+        })
+        .collect()
+}
+
+/// Checks that `needle` occurs exactly once in a grid and returns its `(row, column)` position.
+pub fn find_unique(input: &str, needle: char) -> Result<(usize, usize), String> {
+    match find_all(input, needle).as_slice() {
+        [position] => Ok(*position),
+        positions => Err(format!(
+            "expected exactly one '{needle}', found {}",
+            positions.len()
+        )),
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{find_all, find_unique, grid_chars, rectangular_grid, Validator};
+
+    #[test]
+    fn validator_collects_violations() {
+        let mut validator = Validator::new();
+        validator
+            .check(true, "not reported")
+            .check(false, "reported");
+        assert_eq!(validator.require(Err::<(), _>("required".into())), None);
+        assert_eq!(validator.require(Ok::<_, String>(5)), Some(5));
+        assert_eq!(
+            validator.finish(),
+            Err(vec!["reported".to_string(), "required".to_string()])
+        );
+        assert_eq!(Validator::new().finish(), Ok(()));
+    }
+
+    #[test]
+    fn checks_rectangular_grid() {
+        assert_eq!(rectangular_grid("..#\n#..\n"), Ok((2, 3)));
+        assert_eq!(
+            rectangular_grid("..#\n#.\n...").unwrap_err(),
+            "input is not a rectangular grid: line 2 has 2 columns, expected 3"
+        );
+        assert!(rectangular_grid("").is_err());
+    }
+
+    #[test]
+    fn checks_grid_chars() {
+        assert_eq!(grid_chars(".#\n#.", ".#"), Ok(()));
+        assert_eq!(
+            grid_chars(".#\n#x", ".#").unwrap_err(),
+            "unexpected character 'x' at row 1, column 1, expected one of \".#\""
+        );
+    }
+
+    #[test]
+    fn finds_positions() {
+        assert_eq!(find_all("S.\n.S", 'S'), vec![(0, 0), (1, 1)]);
+        assert_eq!(find_unique("..\n.S", 'S'), Ok((1, 1)));
+        assert_eq!(
+            find_unique("S.\n.S", 'S').unwrap_err(),
+            "expected exactly one 'S', found 2"
+        );
+    }
+}
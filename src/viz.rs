@@ -0,0 +1,411 @@
+//! Visualization of grid states, enabled with the `--visualize` flag of the solution binaries.
+//!
+//! Frames are rendered to the terminal with ANSI colors (`--visualize`), or written as an image
+//! sequence (`--visualize ppm` / `--visualize png`) to the directory given by `--frames <dir>`,
+//! which defaults to `data/viz/<day>`. Solutions emit frames via [`emit`], the frame is only
+//! built if visualization is enabled.
+use std::{
+    env, fs, io,
+    path::PathBuf,
+    sync::{Mutex, OnceLock},
+};
+
+use crate::template::{runner, ANSI_RESET};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const GRAY: Rgb = Rgb(128, 128, 128);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(230, 60, 60);
+    pub const GREEN: Rgb = Rgb(80, 200, 90);
+    pub const YELLOW: Rgb = Rgb(240, 200, 60);
+    pub const BLUE: Rgb = Rgb(70, 130, 230);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub color: Option<Rgb>,
+}
+
+impl Cell {
+    /// Color used when rendering the cell as a pixel.
+    fn pixel(self) -> Rgb {
+        self.color.unwrap_or(match self.ch {
+            '.' | ' ' => Rgb::BLACK,
+            _ => Rgb::GRAY,
+        })
+    }
+}
+
+/// A rectangular grid of colored characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub title: String,
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Frame {
+    /// Creates a frame filled with uncolored `.` cells.
+    #[must_use]
+    pub fn new(title: impl Into<String>, width: usize, height: usize) -> Self {
+        Frame {
+            title: title.into(),
+            width,
+            height,
+            cells: vec![
+                Cell {
+                    ch: '.',
+                    color: None
+                };
+                width * height
+            ],
+        }
+    }
+
+    /// Creates a frame from the lines of a text grid.
+    #[must_use]
+    pub fn from_text(title: impl Into<String>, text: &str) -> Self {
+        let lines: Vec<&str> = text.lines().collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut frame = Frame::new(title, width, lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                frame.set_char(row, col, ch);
+            }
+        }
+        frame
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn get(&self, row: usize, col: usize) -> Option<Cell> {
+        (row < self.height && col < self.width).then(|| self.cells[row * self.width + col])
+    }
+
+    /// Sets a cell, out of bounds positions are ignored.
+    pub fn set(&mut self, row: usize, col: usize, ch: char, color: Option<Rgb>) {
+        if row < self.height && col < self.width {
+            self.cells[row * self.width + col] = Cell { ch, color };
+        }
+    }
+
+    /// Sets the character of a cell and keeps its color.
+    pub fn set_char(&mut self, row: usize, col: usize, ch: char) {
+        if let Some(cell) = self.get(row, col) {
+            self.set(row, col, ch, cell.color);
+        }
+    }
+
+    /// Sets the color of a cell and keeps its character.
+    pub fn highlight(&mut self, row: usize, col: usize, color: Rgb) {
+        if let Some(cell) = self.get(row, col) {
+            self.set(row, col, cell.ch, Some(color));
+        }
+    }
+
+    /// Renders the frame as text with ANSI true color escape codes.
+    #[must_use]
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        for row in self.cells.chunks(self.width.max(1)) {
+            for cell in row {
+                match cell.color {
+                    Some(Rgb(r, g, b)) => {
+                        out.push_str(&format!("\x1b[38;2;{r};{g};{b}m{}{ANSI_RESET}", cell.ch));
+                    }
+                    None => out.push(cell.ch),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    fn pixels(&self, scale: usize) -> Vec<Vec<Rgb>> {
+        let mut rows = Vec::with_capacity(self.height * scale);
+        for row in self.cells.chunks(self.width.max(1)) {
+            let line: Vec<Rgb> = row
+                .iter()
+                .flat_map(|cell| std::iter::repeat_n(cell.pixel(), scale))
+                .collect();
+            for _ in 0..scale {
+                rows.push(line.clone());
+            }
+        }
+        rows
+    }
+
+    /// Encodes the frame as a binary PPM image, each cell is drawn as a `scale`x`scale` square.
+    #[must_use]
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let mut out =
+            format!("P6\n{} {}\n255\n", self.width * scale, self.height * scale).into_bytes();
+        for Rgb(r, g, b) in self.pixels(scale).into_iter().flatten() {
+            out.extend([r, g, b]);
+        }
+        out
+    }
+
+    /// Encodes the frame as an (uncompressed) PNG image, each cell is drawn as a `scale`x`scale` square.
+    #[must_use]
+    pub fn to_png(&self, scale: usize) -> Vec<u8> {
+        let mut raw: Vec<u8> = vec![];
+        for line in self.pixels(scale) {
+            // filter type `None` for every scanline.
+            raw.push(0);
+            for Rgb(r, g, b) in line {
+                raw.extend([r, g, b]);
+            }
+        }
+
+        let mut header = vec![];
+        header.extend(u32::try_from(self.width * scale).unwrap().to_be_bytes());
+        header.extend(u32::try_from(self.height * scale).unwrap().to_be_bytes());
+        // 8 bit depth, RGB color, default compression, filter and interlace methods.
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut out = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        write_png_chunk(&mut out, b"IHDR", &header);
+        write_png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        write_png_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+}
+
+fn write_png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend(u32::try_from(data.len()).unwrap().to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Wraps `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut chunks = data.chunks(0xffff).peekable();
+
+    if chunks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(chunk) = chunks.next() {
+        let is_final = chunks.peek().is_none();
+        let len = u16::try_from(chunk.len()).unwrap();
+        out.push(u8::from(is_final));
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(chunk);
+    }
+
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for byte in data {
+        a = (a + u32::from(*byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend(((b << 16) | a).to_be_bytes());
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Output {
+    Ansi,
+    Ppm(PathBuf),
+    Png(PathBuf),
+}
+
+/// Scale used for image frames, each cell becomes a square of this many pixels.
+const IMAGE_SCALE: usize = 4;
+
+pub struct Visualizer {
+    output: Output,
+    frame_count: usize,
+}
+
+impl Visualizer {
+    #[must_use]
+    pub fn new(output: Output) -> Self {
+        Visualizer {
+            output,
+            frame_count: 0,
+        }
+    }
+
+    /// Parses `--visualize [ansi|ppm|png]` and `--frames <dir>` from command-line arguments.
+    /// Returns [`None`] if visualization was not requested.
+    pub fn from_args(args: &[String], default_dir: &str) -> Result<Option<Self>, String> {
+        let Some(index) = args.iter().position(|x| x == "--visualize") else {
+            return Ok(None);
+        };
+
+        let dir: PathBuf = args
+            .iter()
+            .position(|x| x == "--frames")
+            .map_or(Some(default_dir), |i| args.get(i + 1).map(String::as_str))
+            .ok_or("Unexpected command-line input. Format: --frames <dir>")?
+            .into();
+
+        let output = match args.get(index + 1).map(String::as_str) {
+            Some("ppm") => Output::Ppm(dir),
+            Some("png") => Output::Png(dir),
+            Some("ansi") | None => Output::Ansi,
+            Some(x) if x.starts_with("--") => Output::Ansi,
+            Some(x) => return Err(format!("Unknown visualization output: {x}")),
+        };
+
+        Ok(Some(Visualizer::new(output)))
+    }
+
+    pub fn render(&mut self, frame: &Frame) -> Result<(), io::Error> {
+        match &self.output {
+            Output::Ansi => {
+                println!("{}", frame.title);
+                println!("{}", frame.to_ansi());
+            }
+            Output::Ppm(dir) => {
+                fs::create_dir_all(dir)?;
+                let path = dir.join(format!("frame_{:05}.ppm", self.frame_count));
+                fs::write(path, frame.to_ppm(IMAGE_SCALE))?;
+            }
+            Output::Png(dir) => {
+                fs::create_dir_all(dir)?;
+                let path = dir.join(format!("frame_{:05}.png", self.frame_count));
+                fs::write(path, frame.to_png(IMAGE_SCALE))?;
+            }
+        }
+        self.frame_count += 1;
+        Ok(())
+    }
+}
+
+static VISUALIZER: OnceLock<Option<Mutex<Visualizer>>> = OnceLock::new();
+
+fn visualizer() -> Option<&'static Mutex<Visualizer>> {
+    VISUALIZER
+        .get_or_init(|| {
+            let args: Vec<String> = env::args().collect();
+            // name of the solution binary, i.e. the day.
+            let day = args
+                .first()
+                .and_then(|arg| PathBuf::from(arg).file_name()?.to_str().map(String::from))
+                .unwrap_or_default();
+            let default_dir = format!("data/viz/{day}");
+
+            match Visualizer::from_args(&args, &default_dir) {
+                Ok(visualizer) => visualizer.map(Mutex::new),
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
+/// Whether `--visualize` was passed to the binary.
+pub fn enabled() -> bool {
+    visualizer().is_some()
+}
+
+/// Renders the frame returned by `build` if visualization is enabled. Frames are not rendered
+/// again while the part is benched.
+pub fn emit(build: impl FnOnce() -> Frame) {
+    let Some(visualizer) = visualizer().filter(|_| !runner::is_benching()) else {
+        return;
+    };
+
+    let frame = build();
+    if let Err(e) = visualizer.lock().unwrap().render(&frame) {
+        eprintln!("Failed to write visualization frame: {e}");
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{crc32, Frame, Output, Rgb, Visualizer};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(Into::into).collect()
+    }
+
+    #[test]
+    fn parses_args() {
+        let parse = |s: &str| Visualizer::from_args(&args(s), "viz").map(|v| v.map(|v| v.output));
+        assert_eq!(parse("10 --time"), Ok(None));
+        assert_eq!(parse("10 --visualize"), Ok(Some(Output::Ansi)));
+        assert_eq!(parse("10 --visualize --time"), Ok(Some(Output::Ansi)));
+        assert_eq!(
+            parse("10 --visualize png"),
+            Ok(Some(Output::Png("viz".into())))
+        );
+        assert_eq!(
+            parse("10 --visualize ppm --frames out"),
+            Ok(Some(Output::Ppm("out".into())))
+        );
+        assert!(parse("10 --visualize gif").is_err());
+    }
+
+    #[test]
+    fn renders_ansi() {
+        let mut frame = Frame::from_text("test", "#.\n.#");
+        frame.highlight(1, 1, Rgb(1, 2, 3));
+        assert_eq!(frame.to_ansi(), "#.\n.\x1b[38;2;1;2;3m#\x1b[0m\n");
+    }
+
+    #[test]
+    fn renders_ppm() {
+        let mut frame = Frame::from_text("test", "#.");
+        frame.highlight(0, 1, Rgb::RED);
+        let mut expected = b"P6\n2 1\n255\n".to_vec();
+        expected.extend([128, 128, 128, 230, 60, 60]);
+        assert_eq!(frame.to_ppm(1), expected);
+    }
+
+    #[test]
+    fn renders_png() {
+        let png = Frame::from_text("test", "#.\n.#").to_png(2);
+        assert_eq!(
+            png[..8],
+            [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']
+        );
+        // IHDR chunk with 4x4 pixels.
+        assert_eq!(png[12..16], *b"IHDR");
+        assert_eq!(png[16..24], [0, 0, 0, 4, 0, 0, 0, 4]);
+        assert_eq!(png[png.len() - 8..png.len() - 4], *b"IEND");
+    }
+
+    #[test]
+    fn computes_crc() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+    }
+}
@@ -0,0 +1,533 @@
+//! Compiler for the part sorting workflows of day 19, e.g. `px{a<2006:qkq,m>2090:A,rfg}`.
+//!
+//! Every rule becomes a test node of a DAG, in which each workflow appears once no matter how
+//! many rules send parts to it. Category names are interned, so any names can be used instead of
+//! `x`, `m`, `a` and `s`. Parts are sorted by walking the DAG, and all ratings accepted within a
+//! range are enumerated as disjoint boxes.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::Answer;
+
+/// Where a rule sends a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+    Accept,
+    Reject,
+    /// Index of the next test in [`Workflows::tests`].
+    Test(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Less,
+    Greater,
+}
+
+/// A rule like `a<2006:qkq`. Parts that don't match fall through to `otherwise`, which is the next
+/// rule of the same workflow or its fallback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Test {
+    /// Index into [`Workflows::categories`].
+    pub category: usize,
+    pub op: Op,
+    pub value: u64,
+    pub then: Target,
+    pub otherwise: Target,
+}
+
+impl Test {
+    fn matches(&self, rating: u64) -> bool {
+        match self.op {
+            Op::Less => rating < self.value,
+            Op::Greater => rating > self.value,
+        }
+    }
+
+    /// Splits `range` into the ratings that match and the ones that don't.
+    fn split(&self, range: &RangeInclusive<u64>) -> [Option<RangeInclusive<u64>>; 2] {
+        let (lo, hi) = (*range.start(), *range.end());
+        let non_empty = |lo: u64, hi: u64| (lo <= hi).then_some(lo..=hi);
+        match self.op {
+            Op::Less if self.value == 0 => [None, non_empty(lo, hi)],
+            Op::Less => [
+                non_empty(lo, hi.min(self.value - 1)),
+                non_empty(lo.max(self.value), hi),
+            ],
+            Op::Greater if self.value == u64::MAX => [None, non_empty(lo, hi)],
+            Op::Greater => [
+                non_empty(lo.max(self.value + 1), hi),
+                non_empty(lo, hi.min(self.value)),
+            ],
+        }
+    }
+}
+
+/// Compiled workflows, starting at the workflow named `in`.
+#[derive(Debug, Clone)]
+pub struct Workflows {
+    categories: Vec<String>,
+    tests: Vec<Test>,
+    start: Target,
+}
+
+impl Workflows {
+    /// Category names in the order of their first use.
+    #[must_use]
+    pub fn categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    /// All rules, the targets of a test refer to its index.
+    #[must_use]
+    pub fn tests(&self) -> &[Test] {
+        &self.tests
+    }
+
+    #[must_use]
+    pub fn start(&self) -> Target {
+        self.start
+    }
+
+    /// Sorts a part. Fails if the part has no rating for a category it is tested on.
+    pub fn accepts(&self, part: &Part) -> Result<bool, WorkflowError> {
+        let mut target = self.start;
+        // the DAG is acyclic, so every part ends up accepted or rejected.
+        loop {
+            match target {
+                Target::Accept => return Ok(true),
+                Target::Reject => return Ok(false),
+                Target::Test(i) => {
+                    let test = &self.tests[i];
+                    let category = &self.categories[test.category];
+                    let rating = part.rating(category).ok_or_else(|| {
+                        WorkflowError(format!("part has no rating for category `{category}`"))
+                    })?;
+                    target = if test.matches(rating) {
+                        test.then
+                    } else {
+                        test.otherwise
+                    };
+                }
+            }
+        }
+    }
+
+    /// Disjoint boxes of ratings that are accepted if every category is rated within `bounds`.
+    #[must_use]
+    pub fn accepted_regions(&self, bounds: RangeInclusive<u64>) -> Vec<Region> {
+        let mut accepted = Vec::new();
+        let region = Region {
+            ranges: vec![bounds; self.categories.len()],
+        };
+        let mut stack = vec![(self.start, region)];
+
+        while let Some((target, region)) = stack.pop() {
+            let test = match target {
+                Target::Accept => {
+                    accepted.push(region);
+                    continue;
+                }
+                Target::Reject => continue,
+                Target::Test(i) => &self.tests[i],
+            };
+
+            let [matching, other] = test.split(&region.ranges[test.category]);
+            for (range, next) in [(other, test.otherwise), (matching, test.then)] {
+                if let Some(range) = range {
+                    let mut ranges = region.ranges.clone();
+                    ranges[test.category] = range;
+                    stack.push((next, Region { ranges }));
+                }
+            }
+        }
+
+        accepted
+    }
+
+    /// Fails if a workflow can send parts back to itself.
+    fn check_acyclic(&self) -> Result<(), WorkflowError> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            Active,
+            Done,
+        }
+
+        let mut states = vec![State::New; self.tests.len()];
+        for root in 0..self.tests.len() {
+            if states[root] != State::New {
+                continue;
+            }
+            // iterative depth-first search, a test is done once both targets are.
+            let mut stack = vec![(root, false)];
+            while let Some((i, children_done)) = stack.pop() {
+                if children_done {
+                    states[i] = State::Done;
+                    continue;
+                }
+                match states[i] {
+                    State::Done => continue,
+                    State::Active => return Err(WorkflowError("workflows contain a cycle".into())),
+                    State::New => {}
+                }
+                states[i] = State::Active;
+                stack.push((i, true));
+                for target in [self.tests[i].then, self.tests[i].otherwise] {
+                    if let Target::Test(next) = target {
+                        match states[next] {
+                            State::Active => {
+                                return Err(WorkflowError("workflows contain a cycle".into()))
+                            }
+                            State::New => stack.push((next, false)),
+                            State::Done => {}
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses the workflow section of the puzzle input, one workflow per line.
+impl FromStr for Workflows {
+    type Err = WorkflowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        struct Rule<'a> {
+            category: &'a str,
+            op: Op,
+            value: u64,
+            target: &'a str,
+        }
+        let error = |line: &str| WorkflowError(format!("invalid workflow `{line}`"));
+
+        let mut workflows: Vec<(&str, Vec<Rule>, &str)> = Vec::new();
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (name, body) = line
+                .strip_suffix('}')
+                .and_then(|line| line.split_once('{'))
+                .ok_or_else(|| error(line))?;
+            let mut rules: Vec<&str> = body.split(',').collect();
+            let fallback = rules
+                .pop()
+                .filter(|f| !f.contains(':'))
+                .ok_or_else(|| error(line))?;
+            let rules = rules
+                .into_iter()
+                .map(|rule| {
+                    let (condition, target) = rule.split_once(':')?;
+                    let split = condition.find(['<', '>'])?;
+                    let op = if &condition[split..=split] == "<" {
+                        Op::Less
+                    } else {
+                        Op::Greater
+                    };
+                    Some(Rule {
+                        category: &condition[..split],
+                        op,
+                        value: condition[split + 1..].parse().ok()?,
+                        target,
+                    })
+                })
+                .collect::<Option<Vec<Rule>>>()
+                .ok_or_else(|| error(line))?;
+            workflows.push((name, rules, fallback));
+        }
+
+        // the rules of a workflow are numbered consecutively, starting at `first`.
+        let mut entries: HashMap<&str, (usize, usize, &str)> = HashMap::new();
+        let mut first = 0;
+        for (name, rules, fallback) in &workflows {
+            if entries
+                .insert(name, (first, rules.len(), fallback))
+                .is_some()
+            {
+                return Err(WorkflowError(format!("workflow `{name}` is defined twice")));
+            }
+            first += rules.len();
+        }
+
+        let resolve = |name: &str| -> Result<Target, WorkflowError> {
+            let mut name: &str = name;
+            // workflows without rules forward to their fallback.
+            for _ in 0..=entries.len() {
+                match name {
+                    "A" => return Ok(Target::Accept),
+                    "R" => return Ok(Target::Reject),
+                    _ => {}
+                }
+                let (first, rules, fallback) = entries
+                    .get(name)
+                    .ok_or_else(|| WorkflowError(format!("unknown workflow `{name}`")))?;
+                if *rules > 0 {
+                    return Ok(Target::Test(*first));
+                }
+                name = fallback;
+            }
+            Err(WorkflowError("workflows contain a cycle".into()))
+        };
+
+        let mut categories: Vec<String> = Vec::new();
+        let mut tests = Vec::with_capacity(first);
+        for (name, rules, fallback) in &workflows {
+            let (first, ..) = entries[name];
+            for (i, rule) in rules.iter().enumerate() {
+                let category = match categories.iter().position(|c| c == rule.category) {
+                    Some(category) => category,
+                    None => {
+                        categories.push(rule.category.to_string());
+                        categories.len() - 1
+                    }
+                };
+                let otherwise = if i + 1 < rules.len() {
+                    Target::Test(first + i + 1)
+                } else {
+                    resolve(fallback)?
+                };
+                tests.push(Test {
+                    category,
+                    op: rule.op,
+                    value: rule.value,
+                    then: resolve(rule.target)?,
+                    otherwise,
+                });
+            }
+        }
+
+        let workflows = Workflows {
+            categories,
+            tests,
+            start: resolve("in")?,
+        };
+        workflows.check_acyclic()?;
+        Ok(workflows)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The ratings of a part, e.g. `{x=787,m=2655,a=1222,s=2876}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    ratings: Vec<(String, u64)>,
+}
+
+impl Part {
+    #[must_use]
+    pub fn rating(&self, category: &str) -> Option<u64> {
+        self.ratings
+            .iter()
+            .find(|(name, _)| name == category)
+            .map(|(_, rating)| *rating)
+    }
+
+    /// Sum of all ratings.
+    #[must_use]
+    pub fn total(&self) -> Answer {
+        self.ratings.iter().map(|(_, rating)| *rating).sum()
+    }
+}
+
+impl FromStr for Part {
+    type Err = WorkflowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || WorkflowError(format!("invalid part `{s}`"));
+        let ratings = s
+            .trim()
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .ok_or_else(error)?
+            .split(',')
+            .map(|rating| {
+                let (name, value) = rating.split_once('=')?;
+                Some((name.to_string(), value.parse().ok()?))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(error)?;
+        Ok(Part { ratings })
+    }
+}
+
+/// A box of ratings, with one range per category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    ranges: Vec<RangeInclusive<u64>>,
+}
+
+impl Region {
+    /// Ranges indexed like [`Workflows::categories`].
+    #[must_use]
+    pub fn ranges(&self) -> &[RangeInclusive<u64>] {
+        &self.ranges
+    }
+
+    /// Number of distinct rating combinations in the box.
+    #[must_use]
+    pub fn volume(&self) -> Answer {
+        self.ranges
+            .iter()
+            .map(|range| Answer::from(*range.end()) - *range.start() + 1)
+            .product()
+    }
+
+    /// Whether the ratings, indexed like [`Workflows::categories`], lie in the box.
+    #[must_use]
+    pub fn contains(&self, ratings: &[u64]) -> bool {
+        self.ranges
+            .iter()
+            .zip(ratings)
+            .all(|(range, rating)| range.contains(rating))
+    }
+}
+
+/// An error which can be returned when parsing workflows or parts, or when sorting a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkflowError(String);
+
+impl Error for WorkflowError {}
+
+impl Display for WorkflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Part, Region, Target, WorkflowError, Workflows};
+    use crate::Answer;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}";
+
+    fn example() -> Workflows {
+        EXAMPLE.parse().unwrap()
+    }
+
+    fn part(s: &str) -> Part {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn compiles_workflows() {
+        let workflows = example();
+        assert_eq!(workflows.categories(), &["a", "m", "s", "x"]);
+        // one test per rule, `in` starts with the 8th workflow's first rule.
+        assert_eq!(workflows.tests().len(), 14);
+        assert_eq!(workflows.start(), Target::Test(9));
+    }
+
+    #[test]
+    fn sorts_parts() {
+        let workflows = example();
+        let accepted: Vec<bool> = [
+            "{x=787,m=2655,a=1222,s=2876}",
+            "{x=1679,m=44,a=2067,s=496}",
+            "{x=2036,m=264,a=79,s=2244}",
+            "{x=2461,m=1339,a=466,s=291}",
+            "{x=2127,m=1623,a=2188,s=1013}",
+        ]
+        .iter()
+        .map(|p| workflows.accepts(&part(p)).unwrap())
+        .collect();
+        assert_eq!(accepted, vec![true, false, true, false, true]);
+        assert_eq!(
+            part("{x=787,m=2655,a=1222,s=2876}").total(),
+            Answer::from(7540)
+        );
+
+        let err = workflows.accepts(&part("{x=1,m=2}")).unwrap_err();
+        assert_eq!(err.to_string(), "part has no rating for category `s`");
+    }
+
+    #[test]
+    fn enumerates_accepted_regions() {
+        let regions = example().accepted_regions(1..=4000);
+        let total: Answer = regions.iter().map(Region::volume).sum();
+        assert_eq!(total, Answer::from(167_409_079_868_000_i64));
+        assert!(regions.iter().all(|r| r.ranges().len() == 4));
+    }
+
+    #[test]
+    fn supports_any_categories() {
+        let workflows: Workflows = "in{speed>10:fast,R}\nfast{shiny<1:R,ok}\nok{A}"
+            .parse()
+            .unwrap();
+        assert_eq!(workflows.categories(), &["speed", "shiny"]);
+        assert_eq!(workflows.accepts(&part("{speed=11,shiny=1}")), Ok(true));
+        assert_eq!(workflows.accepts(&part("{speed=11,shiny=0}")), Ok(false));
+
+        let regions = workflows.accepted_regions(0..=20);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].ranges(), &[11..=20, 1..=20]);
+    }
+
+    #[test]
+    fn rejects_invalid_workflows() {
+        let err = |s: &str| s.parse::<Workflows>().unwrap_err();
+        assert_eq!(
+            err("in{x>1:a,A}\na{x<5:in,R}"),
+            WorkflowError("workflows contain a cycle".into())
+        );
+        assert_eq!(
+            err("in{a}\na{in}"),
+            WorkflowError("workflows contain a cycle".into())
+        );
+        assert_eq!(
+            err("in{x>1:b,A}"),
+            WorkflowError("unknown workflow `b`".into())
+        );
+        assert_eq!(
+            err("in{x>1:A}"),
+            WorkflowError("invalid workflow `in{x>1:A}`".into())
+        );
+        assert_eq!(
+            err("in{x=1:A,R}"),
+            WorkflowError("invalid workflow `in{x=1:A,R}`".into())
+        );
+        assert_eq!(err("a{A}"), WorkflowError("unknown workflow `in`".into()));
+        assert!("{x=1,m}".parse::<Part>().is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn regions_partition_accepted_parts(ratings in prop::array::uniform4(1_u64..=4000)) {
+            let workflows = example();
+            let part: Part = format!(
+                "{{x={},m={},a={},s={}}}",
+                ratings[0], ratings[1], ratings[2], ratings[3]
+            )
+            .parse()
+            .unwrap();
+            let indexed: Vec<u64> = workflows
+                .categories()
+                .iter()
+                .map(|c| part.rating(c).unwrap())
+                .collect();
+
+            let containing = workflows
+                .accepted_regions(1..=4000)
+                .iter()
+                .filter(|region| region.contains(&indexed))
+                .count();
+            prop_assert!(containing <= 1);
+            prop_assert_eq!(workflows.accepts(&part).unwrap(), containing == 1);
+        }
+    }
+}